@@ -0,0 +1,123 @@
+//! Benchmarks for the chainbridge pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+
+use crate::Module as Bridge;
+
+/// Largest encoded call benchmarked
+const MAX_CALL_LEN: u32 = 16 * 1024;
+
+benchmarks! {
+    where_clause { where <T as Config>::Proposal: From<frame_system::Call<T>> }
+
+    // The proposal is created and stored without being executed, so the weight covers only the
+    // vote and the storage writes keyed by the call.
+    acknowledge_proposal {
+        let c in 0 .. MAX_CALL_LEN;
+        let src_id = T::ChainId::get().wrapping_add(1);
+        let r_id = T::ResourceIdScheme::derive(src_id, b"remark");
+        let relayer: T::AccountId = account("relayer", 0, 0);
+        Bridge::<T>::whitelist(src_id)?;
+        Bridge::<T>::register_resource(r_id, b"System.remark".to_vec())?;
+        Bridge::<T>::register_relayer(relayer.clone())?;
+        <RelayerActiveFrom<T>>::remove(&relayer);
+        Bridge::<T>::set_relayer_threshold(2)?;
+        let call: <T as Config>::Proposal = frame_system::Call::<T>::remark(vec![0; c as usize]).into();
+        let proof = vec![0; T::MaxVoteProofLen::get() as usize];
+    }: _(RawOrigin::Signed(relayer), 1, SrcChainId(src_id), r_id, Box::new(call), proof)
+    verify {
+        assert_eq!(Bridge::<T>::total_proposals(), 1);
+    }
+
+    // Every relayer has voted in favour with a proof, which all have to be removed. The
+    // threshold is above the number of relayers so the proposal stays open until it expires.
+    expire_proposal {
+        let src_id = T::ChainId::get().wrapping_add(1);
+        let r_id = T::ResourceIdScheme::derive(src_id, b"remark");
+        Bridge::<T>::whitelist(src_id)?;
+        Bridge::<T>::register_resource(r_id, b"System.remark".to_vec())?;
+        Bridge::<T>::set_relayer_threshold(T::MaxRelayers::get() + 1)?;
+        let call: <T as Config>::Proposal = frame_system::Call::<T>::remark(vec![]).into();
+        let proof = vec![0; T::MaxVoteProofLen::get() as usize];
+        for i in 0 .. T::MaxRelayers::get() {
+            let relayer: T::AccountId = account("relayer", i, 0);
+            Bridge::<T>::register_relayer(relayer.clone())?;
+            <RelayerActiveFrom<T>>::remove(&relayer);
+            Bridge::<T>::acknowledge_proposal(
+                RawOrigin::Signed(relayer).into(),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(call.clone()),
+                proof.clone(),
+            )?;
+        }
+        let now = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(now + T::ProposalLifetime::get());
+        let caller: T::AccountId = account("caller", 0, 0);
+    }: _(RawOrigin::Signed(caller), SrcChainId(src_id), 1, Box::new(call.clone()))
+    verify {
+        assert_eq!(Bridge::<T>::votes(src_id, (1, call)), None);
+        assert_eq!(Bridge::<T>::proposal_outcome(src_id, 1), Some(ProposalStatus::Rejected));
+    }
+
+    // Each chain owns one resource, and the existing state is as large as the imported one.
+    import_state {
+        let r in 1 .. T::MaxRelayers::get();
+        let c in 1 .. T::MaxBatchSize::get();
+        let s in 0 .. T::MaxBatchSize::get();
+        let chains: Vec<(ChainId, DepositNonce)> = (0 .. c)
+            .map(|i| (T::ChainId::get().wrapping_add(1 + i as u8), 0))
+            .collect();
+        let snapshot = BridgeStateSnapshot {
+            relayers: (0 .. r).map(|i| account("relayer", i, 0)).collect(),
+            threshold: 1,
+            resources: (0 .. s)
+                .map(|i| {
+                    let (chain, _) = chains[(i % c) as usize];
+                    (T::ResourceIdScheme::derive(chain, &i.to_le_bytes()), b"System.remark".to_vec(), true)
+                })
+                .collect(),
+            chains,
+        };
+        Bridge::<T>::restore_state(snapshot.clone())?;
+    }: _(RawOrigin::Root, snapshot)
+    verify {
+        assert_eq!(Bridge::<T>::relayer_count(), r);
+        assert_eq!(Bridge::<T>::chain_list().len(), c as usize);
+        assert_eq!(Bridge::<T>::resource_count(), s);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn acknowledge_proposal() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_acknowledge_proposal::<Test>());
+        });
+    }
+
+    #[test]
+    fn expire_proposal() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_expire_proposal::<Test>());
+        });
+    }
+
+    #[test]
+    fn import_state() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_import_state::<Test>());
+        });
+    }
+}
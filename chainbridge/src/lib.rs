@@ -1,31 +1,64 @@
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
+// `decl_module!` needs a higher limit to expand this many calls.
+#![recursion_limit = "256"]
 
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage,
-    dispatch::DispatchResult,
+    debug, decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, PostDispatchInfo},
     ensure,
-    traits::{EnsureOrigin, Get},
-    weights::{GetDispatchInfo, Pays},
-    Parameter,
+    storage::{unhashed, StoragePrefixedMap},
+    traits::{EnsureOrigin, Filter, Get, GetCallMetadata, GetPalletVersion, PalletVersion},
+    weights::{GetDispatchInfo, Pays, Weight},
+    CloneNoBound, EqNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
 };
 
 use frame_system::{self as system, ensure_root, ensure_signed};
 use sp_core::U256;
-use sp_runtime::traits::{AccountIdConversion, Dispatchable};
-use sp_runtime::{ModuleId, RuntimeDebug};
-use sp_std::prelude::*;
+use sp_runtime::traits::{
+    AccountIdConversion, Dispatchable, Hash as HashT, Member, Saturating, Zero,
+};
+use sp_runtime::{ModuleId, Perbill, RuntimeDebug};
+use sp_std::{convert::TryFrom, fmt, marker::PhantomData, ops::Deref, prelude::*};
 
-use codec::{Decode, Encode, EncodeLike};
+use codec::{Decode, Encode, EncodeLike, Input};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
+mod benchmarking;
 mod mock;
+pub mod runtime_api;
 mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
 
 const DEFAULT_RELAYER_THRESHOLD: u32 = 1;
+/// Version of the bridge message formats, bumped on incompatible changes
+pub const BRIDGE_VERSION: u32 = 1;
 const MODULE_ID: ModuleId = ModuleId(*b"cb/bridg");
+const FEE_MODULE_ID: ModuleId = ModuleId(*b"cb/fees_");
+const LOG_TARGET: &str = "runtime::chainbridge";
 
 pub type ChainId = u8;
 pub type DepositNonce = u64;
+
+/// The chain a proposal originates from.
+///
+/// This is encoded identically to a `ChainId`, but is a distinct type so that it can't be
+/// transposed with the adjacent `DepositNonce` in the voting calls:
+///
+/// ```compile_fail
+/// use chainbridge::{DepositNonce, SrcChainId};
+///
+/// fn vote(_nonce: DepositNonce, _src_id: SrcChainId) {}
+///
+/// let nonce: DepositNonce = 1;
+/// let src_id = SrcChainId(2);
+/// vote(src_id, nonce);
+/// ```
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SrcChainId(pub ChainId);
 pub type ResourceId = [u8; 32];
 
 /// Helper function to concatenate a chain ID and some bytes to produce a resource ID.
@@ -40,6 +73,125 @@ pub fn derive_resource_id(chain: u8, id: &[u8]) -> ResourceId {
     return r_id;
 }
 
+/// Checks whether a resource ID was derived for the given chain, i.e. its last byte is the chain ID.
+pub fn resource_matches_chain(r_id: ResourceId, chain: ChainId) -> bool {
+    r_id[31] == chain
+}
+
+/// Layout of resource IDs, which depends on the chains being bridged.
+pub trait ResourceIdScheme {
+    /// Produces the resource ID for `id` on `chain`
+    fn derive(chain: ChainId, id: &[u8]) -> ResourceId;
+    /// Checks whether a resource ID was derived for the given chain
+    fn matches_chain(r_id: ResourceId, chain: ChainId) -> bool;
+}
+
+/// The scheme used by the ChainBridge Solidity contracts, see `derive_resource_id`.
+pub struct EvmResourceId;
+impl ResourceIdScheme for EvmResourceId {
+    fn derive(chain: ChainId, id: &[u8]) -> ResourceId {
+        derive_resource_id(chain, id)
+    }
+
+    fn matches_chain(r_id: ResourceId, chain: ChainId) -> bool {
+        resource_matches_chain(r_id, chain)
+    }
+}
+
+/// A `Vec` that cannot hold more than `S::get()` elements.
+///
+/// The bound is enforced both when pushing and when decoding, so the worst-case size of a stored
+/// value is known up front.
+pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S: Get<u32>> BoundedVec<T, S> {
+    /// Maximum number of elements that can be held
+    pub fn bound() -> usize {
+        S::get() as usize
+    }
+
+    /// Appends an element, failing if the vector is already full
+    #[allow(clippy::result_unit_err)]
+    pub fn try_push(&mut self, element: T) -> Result<(), ()> {
+        if self.0.len() < Self::bound() {
+            self.0.push(element);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Consumes self, returning the inner `Vec`
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
+    type Error = ();
+
+    fn try_from(v: Vec<T>) -> Result<Self, Self::Error> {
+        if v.len() <= Self::bound() {
+            Ok(Self(v, PhantomData))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl<T, S> Default for BoundedVec<T, S> {
+    fn default() -> Self {
+        Self(Vec::new(), PhantomData)
+    }
+}
+
+impl<T: Clone, S> Clone for BoundedVec<T, S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedVec<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, S> Eq for BoundedVec<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedVec<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, S> Deref for BoundedVec<T, S> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Encode, S> Encode for BoundedVec<T, S> {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+        self.0.using_encoded(f)
+    }
+}
+
+impl<T: Encode, S> EncodeLike for BoundedVec<T, S> {}
+
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let inner = Vec::<T>::decode(input)?;
+        Self::try_from(inner).map_err(|_| "BoundedVec exceeds its limit".into())
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub enum ProposalStatus {
     Initiated,
@@ -47,22 +199,113 @@ pub enum ProposalStatus {
     Rejected,
 }
 
+/// Outcome of a proposal that has enough votes to be both approved and rejected, which can
+/// happen when relayers are removed after voting
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum TieBreakMode {
+    ApproveWins,
+    RejectWins,
+}
+
+/// Handling of approved proposals whose call no longer matches the method registered for
+/// their resource
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum MethodMismatchMode {
+    Reject,
+    LogAndProceed,
+}
+
+/// Relayer set, whitelisted chains and resources of the bridge, for backing up and restoring
+/// its configuration
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BridgeStateSnapshot<AccountId> {
+    pub relayers: Vec<AccountId>,
+    pub threshold: u32,
+    /// Whitelisted chains with their current deposit nonce
+    pub chains: Vec<(ChainId, DepositNonce)>,
+    /// Registered resources with their method and whether they are enabled
+    pub resources: Vec<(ResourceId, Vec<u8>, bool)>,
+}
+
+/// Details of a recent outbound transfer, from which its event can be emitted again
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub struct ProposalVotes<AccountId, BlockNumber> {
-    pub votes_for: Vec<AccountId>,
-    pub votes_against: Vec<AccountId>,
+pub enum TransferRecord<AccountId> {
+    /// (resource_id, amount, recipient, metadata, source)
+    Fungible(ResourceId, U256, Vec<u8>, Vec<u8>, Option<AccountId>),
+    /// (resource_id, token_id, recipient, metadata, source)
+    NonFungible(ResourceId, Vec<u8>, Vec<u8>, Vec<u8>, Option<AccountId>),
+    /// (resource_id, metadata, source)
+    Generic(ResourceId, Vec<u8>, Option<AccountId>),
+    /// (resource_id, metadata, reply_nonce)
+    GenericWithReply(ResourceId, Vec<u8>, DepositNonce),
+}
+
+/// Layout of `ProposalVotes` up to version 0.0.2 of the pallet, before votes were bounded and
+/// the resource, note and creation block were recorded
+#[derive(Decode)]
+struct ProposalVotesV0<AccountId, BlockNumber> {
+    votes_for: Vec<AccountId>,
+    votes_against: Vec<AccountId>,
+    status: ProposalStatus,
+    expiry: BlockNumber,
+}
+
+#[derive(PartialEqNoBound, EqNoBound, CloneNoBound, Encode, Decode, RuntimeDebugNoBound)]
+pub struct ProposalVotes<AccountId: Member, BlockNumber: Member, MaxVotes: Get<u32>> {
+    pub votes_for: BoundedVec<AccountId, MaxVotes>,
+    pub votes_against: BoundedVec<AccountId, MaxVotes>,
     pub status: ProposalStatus,
     pub expiry: BlockNumber,
+    pub resource_id: ResourceId,
+    pub note: Vec<u8>,
+    pub created_at: BlockNumber,
 }
 
-impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
+impl<A: Member, B: Member + PartialOrd + Default, S: Get<u32>> ProposalVotes<A, B, S> {
     /// Attempts to mark the proposal as approve or rejected.
+    /// No decision is made until at least `quorum` votes have been cast, and `tie_break` decides
+    /// if both outcomes are possible.
     /// Returns true if the status changes from active.
-    fn try_to_complete(&mut self, threshold: u32, total: u32) -> ProposalStatus {
-        if self.votes_for.len() >= threshold as usize {
+    fn try_to_complete(
+        &mut self,
+        threshold: u32,
+        quorum: u32,
+        total: u32,
+        tie_break: TieBreakMode,
+    ) -> ProposalStatus {
+        self.try_to_complete_weighted(
+            self.votes_for.len() as u128,
+            self.votes_against.len() as u128,
+            threshold as u128,
+            total as u128,
+            quorum,
+            tie_break,
+        )
+    }
+
+    /// Like `try_to_complete`, but compares the weight behind each side of the vote rather than
+    /// the number of votes. `quorum` is still a number of votes.
+    fn try_to_complete_weighted(
+        &mut self,
+        weight_for: u128,
+        weight_against: u128,
+        threshold: u128,
+        total: u128,
+        quorum: u32,
+        tie_break: TieBreakMode,
+    ) -> ProposalStatus {
+        let approved = weight_for >= threshold;
+        let rejected = total >= threshold && weight_against.saturating_add(threshold) > total;
+        if total == 0 {
+            // Nobody is left to decide the proposal, so it stays open until it expires
+            ProposalStatus::Initiated
+        } else if ((self.votes_for.len() + self.votes_against.len()) as u32) < quorum {
+            ProposalStatus::Initiated
+        } else if approved && (!rejected || tie_break == TieBreakMode::ApproveWins) {
             self.status = ProposalStatus::Approved;
             ProposalStatus::Approved
-        } else if total >= threshold && self.votes_against.len() as u32 + threshold > total {
+        } else if rejected {
             self.status = ProposalStatus::Rejected;
             ProposalStatus::Rejected
         } else {
@@ -86,58 +329,318 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
     }
 }
 
-impl<AccountId, BlockNumber: Default> Default for ProposalVotes<AccountId, BlockNumber> {
+impl<AccountId: Member, BlockNumber: Member + Default, MaxVotes: Get<u32>> Default
+    for ProposalVotes<AccountId, BlockNumber, MaxVotes>
+{
     fn default() -> Self {
         Self {
-            votes_for: vec![],
-            votes_against: vec![],
+            votes_for: BoundedVec::default(),
+            votes_against: BoundedVec::default(),
             status: ProposalStatus::Initiated,
             expiry: BlockNumber::default(),
+            resource_id: ResourceId::default(),
+            note: Vec::new(),
+            created_at: BlockNumber::default(),
         }
     }
 }
 
+/// Decides whether an inbound proposal may be executed, allowing the runtime to throttle
+/// the value entering the chain.
+pub trait InboundRateLimiter<BlockNumber> {
+    /// Called once a proposal has enough votes to be approved. Returning false defers execution,
+    /// returning true counts the proposal towards any limits.
+    fn allow(src_id: ChainId, resource_id: ResourceId, now: BlockNumber) -> bool;
+}
+
+impl<BlockNumber> InboundRateLimiter<BlockNumber> for () {
+    fn allow(_src_id: ChainId, _resource_id: ResourceId, _now: BlockNumber) -> bool {
+        true
+    }
+}
+
+/// Stake backing each relayer's votes, for bridges that approve proposals by stake rather than
+/// by number of relayers.
+pub trait RelayerStake<AccountId> {
+    /// Stake of a single relayer
+    fn stake_of(who: &AccountId) -> u128;
+    /// Combined stake of the whole relayer set
+    fn total_stake() -> u128;
+}
+
+/// Gives every relayer an equal stake of one.
+pub struct EqualStake<T>(PhantomData<T>);
+impl<T: Config> RelayerStake<T::AccountId> for EqualStake<T> {
+    fn stake_of(_who: &T::AccountId) -> u128 {
+        1
+    }
+
+    fn total_stake() -> u128 {
+        <Module<T>>::relayer_count() as u128
+    }
+}
+
+/// Called after an approved proposal has executed successfully.
+pub trait OnProposalApproved {
+    /// Returns the weight used, which is added to the post-dispatch weight of the extrinsic that
+    /// executed the proposal. That weight can't exceed the extrinsic's declared weight, so any
+    /// weight used here should also be included in the proposal's own weight.
+    fn on_proposal_approved(src_id: ChainId, nonce: DepositNonce) -> Weight;
+}
+
+impl OnProposalApproved for () {
+    fn on_proposal_approved(_src_id: ChainId, _nonce: DepositNonce) -> Weight {
+        0
+    }
+}
+
+/// Called after every proposal is resolved, whether it was approved or rejected.
+pub trait OnProposalResolved {
+    /// `executed` is whether the proposal's call was dispatched, which is never the case for
+    /// rejected proposals.
+    fn on_proposal_resolved(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        resource_id: ResourceId,
+        status: ProposalStatus,
+        executed: bool,
+    );
+}
+
+impl OnProposalResolved for () {
+    fn on_proposal_resolved(
+        _src_id: ChainId,
+        _nonce: DepositNonce,
+        _resource_id: ResourceId,
+        _status: ProposalStatus,
+        _executed: bool,
+    ) {
+    }
+}
+
+/// Called after a chain has been whitelisted.
+pub trait OnChainWhitelisted {
+    fn on_chain_whitelisted(id: ChainId);
+}
+
+impl OnChainWhitelisted for () {
+    fn on_chain_whitelisted(_id: ChainId) {}
+}
+
+/// Called after a chain has been removed from the whitelist.
+pub trait OnChainRemoved {
+    fn on_chain_removed(id: ChainId);
+}
+
+impl OnChainRemoved for () {
+    fn on_chain_removed(_id: ChainId) {}
+}
+
 pub trait Config: system::Config {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
     /// Origin used to administer the pallet
     type AdminOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin allowed to reject a proposal immediately, regardless of its votes
+    type VetoOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin allowed to initiate outbound transfers directly, rather than through another pallet
+    type TransferOrigin: EnsureOrigin<Self::Origin>;
     /// Proposed dispatchable call
-    type Proposal: Parameter + Dispatchable<Origin = Self::Origin> + EncodeLike + GetDispatchInfo;
+    type Proposal: Parameter
+        + Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+        + EncodeLike
+        + GetDispatchInfo
+        + GetCallMetadata;
     /// The identifier for this chain.
     /// This must be unique and must not collide with existing IDs within a set of bridged chains.
     type ChainId: Get<ChainId>;
 
     type ProposalLifetime: Get<Self::BlockNumber>;
+    /// Maximum number of votes that can be recorded on either side of a proposal.
+    /// This should be at least the number of relayers expected to be in the set.
+    type MaxRelayers: Get<u32>;
+    /// Maximum length of the metadata attached to a fungible transfer
+    type MaxFungibleMetadataLen: Get<u32>;
+    /// Maximum length of the token ID of a nonfungible transfer
+    type MaxTokenIdLen: Get<u32>;
+    /// Maximum length of the metadata attached to a nonfungible transfer
+    type MaxNftMetadataLen: Get<u32>;
+    /// Maximum number of recipients in a single `transfer_fungible_multi`
+    type MaxFungibleRecipients: Get<u32>;
+    /// Maximum number of entries in the input of any batch call, such as `onboard_chain`,
+    /// `whitelist_chains_with_nonce` or `transfer_fungible_multi`
+    type MaxBatchSize: Get<u32>;
+    /// Maximum length of the note a relayer can attach when creating a proposal
+    type MaxProposalNoteLen: Get<u32>;
+    /// Maximum length of the source event proof a relayer can attach to a vote
+    type MaxVoteProofLen: Get<u32>;
+    /// Maximum length of the human-readable name of a chain
+    type MaxChainNameLen: Get<u32>;
+    /// Number of the latest outbound transfers kept in `RecentTransfers`
+    type MaxRecentTransfers: Get<u32>;
+    /// Maximum number of proposals, across all chains, that can be awaiting a decision at once
+    type MaxTotalActiveProposals: Get<u32>;
+    /// Amount the deposit nonce of a destination chain is increased by for each transfer.
+    /// Must be greater than zero, use 1 for sequential nonces.
+    type NonceStep: Get<DepositNonce>;
+    /// Number of outbound transfers in a single block above which `HighTransferVolume` is emitted
+    type TransferVolumeAlertThreshold: Get<u32>;
+    /// Number of blocks that must pass after a proposal is created before it can be executed,
+    /// to allow for short reorgs on the source chain
+    type MinConfirmations: Get<Self::BlockNumber>;
+    /// Whether to emit `DuplicateVoteAttempt` when a relayer votes twice on a proposal
+    type ReportDuplicateVotes: Get<bool>;
+    /// Throttles execution of approved inbound proposals
+    type InboundRateLimiter: InboundRateLimiter<Self::BlockNumber>;
+    /// Whether votes in favour of a proposal must include a proof of the source event
+    type RequireVoteProof: Get<bool>;
+    /// Whether to reject votes in favour of proposals with a nonce at or below the highest
+    /// nonce already executed for the source chain. Leave disabled for bridges that allow gaps.
+    type RejectStaleNonces: Get<bool>;
+    /// Whether approved proposals must still have their resource registered and enabled to be
+    /// executed. Otherwise a proposal can be finalized after its resource has been removed.
+    type RejectRemovedResources: Get<bool>;
+    /// Whether a vote against a proposal that doesn't exist yet creates it.
+    /// If disabled, `reject_proposal` fails with `ProposalDoesNotExist` instead.
+    type AllowRejectCreatesProposal: Get<bool>;
+    /// Handling of approved proposals whose call doesn't match their resource's method.
+    /// Use `MethodMismatchMode::Reject` unless resources are expected to be re-registered
+    /// while proposals are pending.
+    type MethodMismatch: Get<MethodMismatchMode>;
+    /// Minimum number of blocks between changes to the chain whitelist
+    type WhitelistChangeCooldown: Get<Self::BlockNumber>;
+    /// Whether `onboard_chain` emits an event for each step alongside `ChainOnboarded`
+    type VerboseOnboarding: Get<bool>;
+    /// Whether `VoteFor` and `VoteAgainst` include the proposal's updated vote counts
+    type VerboseVoteEvents: Get<bool>;
+    /// Whether assigning a relayer to a chain also whitelists the chain if it isn't already
+    type AutoWhitelistOnRelayerAssign: Get<bool>;
+    /// Calls that may be proposed and executed by the bridge. Checked when proposals are voted
+    /// for and again before execution, so calls filtered after voting aren't dispatched.
+    type ProposalFilter: Filter<Self::Proposal>;
+    /// Outcome of proposals that could be both approved and rejected.
+    /// Use `TieBreakMode::ApproveWins` to keep the original behaviour.
+    type TieBreak: Get<TieBreakMode>;
+    /// Stake backing each relayer's votes, used when `StakeThreshold` is set
+    type StakeProvider: RelayerStake<Self::AccountId>;
+    /// Fraction of the total relayer stake that must vote in favour of a proposal for it to be
+    /// approved. If `None` the relayer threshold is used instead.
+    type StakeThreshold: Get<Option<Perbill>>;
+    /// Number of blocks after being added before a relayer can vote
+    type RelayerActivationDelay: Get<Self::BlockNumber>;
+    /// Hook for post-processing of executed proposals
+    type OnProposalApproved: OnProposalApproved;
+    /// Hook for every approved or rejected proposal, along with whether it was executed
+    type OnProposalResolved: OnProposalResolved;
+    /// Number of nested proposal executions within which outbound transfers are still allowed.
+    /// A value of zero prevents executed proposals from initiating transfers at all.
+    type MaxBridgeDepth: Get<u8>;
+    /// Hook for chains being whitelisted
+    type OnChainWhitelisted: OnChainWhitelisted;
+    /// Hook for chains being removed from the whitelist
+    type OnChainRemoved: OnChainRemoved;
+    /// Weight information for extrinsics in this pallet
+    type WeightInfo: WeightInfo;
+    /// Layout of resource IDs. Use `EvmResourceId` for chains running the ChainBridge contracts.
+    type ResourceIdScheme: ResourceIdScheme;
 }
 
 decl_event! {
-    pub enum Event<T> where <T as frame_system::Config>::AccountId {
+    pub enum Event<T> where
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        <T as frame_system::Config>::Hash
+    {
         /// Vote threshold has changed (new_threshold)
         RelayerThresholdChanged(u32),
-        /// Chain now available for transfers (chain_id)
-        ChainWhitelisted(ChainId),
+        /// Vote quorum has changed (new_quorum)
+        QuorumChanged(u32),
+        /// Minimum vote threshold for a resource has changed, zero if cleared (resource_id, threshold)
+        ResourceThresholdChanged(ResourceId, u32),
+        /// Proposal lifetime override for a chain has changed, zero if cleared (chain_id, lifetime)
+        ChainProposalLifetimeChanged(ChainId, BlockNumber),
+        /// Proposal lifetime of chains without an override has changed, zero if cleared (lifetime)
+        ProposalLifetimeChanged(BlockNumber),
+        /// Chain now available for transfers (chain_id, name if set)
+        ChainWhitelisted(ChainId, Option<Vec<u8>>),
+        /// Chain now available for transfers along with its resources (chain_id, resource_count)
+        ChainOnboarded(ChainId, u32),
+        /// Human-readable name of a chain was set, empty if cleared (chain_id, name)
+        ChainNameSet(ChainId, Vec<u8>),
+        /// Chain no longer available for transfers (chain_id, last_nonce)
+        ChainRemoved(ChainId, DepositNonce),
         /// Relayer added to set
         RelayerAdded(AccountId),
         /// Relayer removed from set
         RelayerRemoved(AccountId),
-        /// FunglibleTransfer is for relaying fungibles (dest_id, nonce, resource_id, amount, recipient, metadata)
-        FungibleTransfer(ChainId, DepositNonce, ResourceId, U256, Vec<u8>),
-        /// NonFungibleTransfer is for relaying NFTS (dest_id, nonce, resource_id, token_id, recipient, metadata)
-        NonFungibleTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>, Vec<u8>, Vec<u8>),
-        /// GenericTransfer is for a generic data payload (dest_id, nonce, resource_id, metadata)
-        GenericTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>),
-        /// Vote submitted in favour of proposal
-        VoteFor(ChainId, DepositNonce, AccountId),
-        /// Vot submitted against proposal
-        VoteAgainst(ChainId, DepositNonce, AccountId),
+        /// Relayer assigned to relay from a chain (relayer, chain_id)
+        RelayerChainAssigned(AccountId, ChainId),
+        /// Relayer set replaced by an emergency reset (new_relayers)
+        RelayerSetReplaced(Vec<AccountId>),
+        /// Relayer set was modified (relayer_set_hash)
+        RelayerSetChanged(Hash),
+        /// Account added to the admin members
+        AdminAdded(AccountId),
+        /// Account removed from the admin members
+        AdminRemoved(AccountId),
+        /// Proposals that could no longer be decoded were removed (src_id, count)
+        UndecodableVotesDropped(ChainId, u32),
+        /// FunglibleTransfer is for relaying fungibles (dest_id, nonce, resource_id, amount, recipient, metadata, source)
+        FungibleTransfer(ChainId, DepositNonce, ResourceId, U256, Vec<u8>, Vec<u8>, Option<AccountId>),
+        /// NonFungibleTransfer is for relaying NFTS (dest_id, nonce, resource_id, token_id, recipient, metadata, source)
+        NonFungibleTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>, Vec<u8>, Vec<u8>, Option<AccountId>),
+        /// GenericTransfer is for a generic data payload (dest_id, nonce, resource_id, metadata, source)
+        GenericTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>, Option<AccountId>),
+        /// GenericTransfer expecting a reply (dest_id, nonce, resource_id, metadata, reply_nonce)
+        GenericTransferWithReply(ChainId, DepositNonce, ResourceId, Vec<u8>, DepositNonce),
+        /// New proposal created with an optional note from the relayer
+        /// (src_id, nonce, proposal_hash, note)
+        ProposalCreated(ChainId, DepositNonce, Hash, Vec<u8>),
+        /// Vote submitted in favour of proposal, with the updated (votes_for, votes_against)
+        /// counts if `VerboseVoteEvents` is set
+        VoteFor(ChainId, DepositNonce, AccountId, Option<(u32, u32)>),
+        /// Vot submitted against proposal, with the updated (votes_for, votes_against)
+        /// counts if `VerboseVoteEvents` is set
+        VoteAgainst(ChainId, DepositNonce, AccountId, Option<(u32, u32)>),
+        /// Relayer attempted to vote on a proposal it has already voted on
+        DuplicateVoteAttempt(ChainId, DepositNonce, AccountId),
         /// Voting successful for a proposal
         ProposalApproved(ChainId, DepositNonce),
         /// Voting rejected a proposal
         ProposalRejected(ChainId, DepositNonce),
+        /// Proposal expired without a decision and was removed
+        ProposalExpired(ChainId, DepositNonce),
         /// Execution of call succeeded
         ProposalSucceeded(ChainId, DepositNonce),
+        /// Actual weight used by an executed call (src_id, nonce, weight)
+        ProposalExecuted(ChainId, DepositNonce, Weight),
         /// Execution of call failed
         ProposalFailed(ChainId, DepositNonce),
+        /// Approved call wasn't dispatched as it is no longer allowed by `ProposalFilter` (src_id, nonce)
+        ProposalFilteredAtExecution(ChainId, DepositNonce),
+        /// Outbound transfers in this block exceeded the alert threshold (transfer_count)
+        HighTransferVolume(u32),
+        /// Bridge protocol version in use (version)
+        BridgeVersion(u32),
+        /// Global transfer sequence was overwritten by the admin (new_seq)
+        TransferSeqReset(u64),
+        /// Event of a past outbound transfer was emitted again, directly before this (dest_id, nonce)
+        TransferReplayed(ChainId, DepositNonce),
+        /// Relayer set, chains and resources were restored from a snapshot
+        StateImported,
+        /// Transfers and proposals have been halted
+        BridgePaused,
+        /// Transfers and proposals have been resumed
+        BridgeUnpaused,
+        /// Transfers to and proposals from a chain have been halted (chain_id)
+        ChainPaused(ChainId),
+        /// Transfers to and proposals from a chain have been resumed (chain_id)
+        ChainUnpaused(ChainId),
+        /// Transfers and proposals of a resource have been halted (resource_id)
+        ResourcePaused(ResourceId),
+        /// Transfers and proposals of a resource have been resumed (resource_id)
+        ResourceUnpaused(ResourceId),
+        /// Relayer signalled that it is online (relayer, block_number)
+        RelayerHeartbeat(AccountId, BlockNumber),
     }
 }
 
@@ -147,32 +650,94 @@ decl_error! {
         ThresholdNotSet,
         /// Provided chain Id is not valid
         InvalidChainId,
-        /// Relayer threshold cannot be 0
+        /// Relayer threshold cannot be 0, or exceed the size of a new relayer set
         InvalidThreshold,
         /// Interactions with this chain is not permitted
         ChainNotWhitelisted,
         /// Chain has already been enabled
         ChainAlreadyWhitelisted,
+        /// Chain whitelist was changed too recently, see `WhitelistChangeCooldown`
+        WhitelistChangeTooSoon,
         /// Resource ID provided isn't mapped to anything
         ResourceDoesNotExist,
+        /// Resource ID provided doesn't belong to a chain it can be used with, or doesn't match
+        /// the one the proposal was created with
+        ResourceChainMismatch,
+        /// Proposed call isn't allowed by `ProposalFilter`
+        ProposalFiltered,
+        /// Resource ID has been temporarily disabled
+        ResourceDisabled,
         /// Relayer already in set
         RelayerAlreadyExists,
         /// Provided accountId is not a relayer
         RelayerInvalid,
+        /// Account is already an admin member
+        AdminAlreadyExists,
+        /// Provided accountId is not an admin member
+        AdminInvalid,
         /// Protected operation, must be performed by relayer
         MustBeRelayer,
         /// Relayer has already submitted some vote for this proposal
         RelayerAlreadyVoted,
         /// A proposal with these parameters has already been submitted
         ProposalAlreadyExists,
+        /// `MaxTotalActiveProposals` proposals are already awaiting a decision
+        TooManyActiveProposals,
         /// No proposal with the ID was found
         ProposalDoesNotExist,
         /// Cannot complete proposal, needs more votes
         ProposalNotComplete,
         /// Proposal has either failed or succeeded
         ProposalAlreadyComplete,
+        /// Proposal has already been approved, no further votes are needed
+        ProposalAlreadyApproved,
+        /// Proposal has already been rejected and cannot be voted on
+        ProposalAlreadyRejected,
         /// Lifetime of proposal has been exceeded
         ProposalExpired,
+        /// Relayer set or proposal votes have reached `MaxRelayers`
+        TooManyRelayers,
+        /// Transfer metadata exceeds the maximum allowed length
+        MetadataTooLong,
+        /// Proposal note exceeds the maximum allowed length
+        NoteTooLong,
+        /// Bridge is paused, transfers and proposals are not permitted
+        BridgeIsPaused,
+        /// Chain is paused, transfers to it and proposals from it are not permitted
+        ChainPaused,
+        /// Resource is paused, transfers and proposals of it are not permitted
+        ResourcePaused,
+        /// No reply is expected for the given chain and nonce
+        ReplyNotPending,
+        /// Transfer isn't among the recent transfers, so it can't be emitted again
+        TransferNotFound,
+        /// Proposal has enough votes but execution is currently rate limited
+        RateLimited,
+        /// Proposal has enough votes but `MinConfirmations` blocks haven't passed since its creation
+        AwaitingConfirmations,
+        /// Transfer has no recipients or more than `MaxFungibleRecipients`
+        InvalidRecipientCount,
+        /// Batch input has more than `MaxBatchSize` entries
+        BatchTooLarge,
+        /// A proof of the source event is required but none was provided
+        MissingProof,
+        /// Vote proof exceeds the maximum allowed length
+        ProofTooLong,
+        /// Chain name exceeds the maximum allowed length
+        NameTooLong,
+        /// Proposal nonce is not above the highest nonce already executed for the source chain,
+        /// or a transfer with the same nonce and resource has already been executed
+        NonceAlreadyUsed,
+        /// Relayer was added too recently to vote, see `RelayerActivationDelay`
+        RelayerNotYetActive,
+        /// Token ID of a nonfungible transfer exceeds the maximum allowed length
+        TokenIdTooLong,
+        /// Transfer was initiated from a proposal nested deeper than `MaxBridgeDepth`
+        MaxDepthExceeded,
+        /// Proposal can't be expired before its lifetime has passed
+        ProposalNotExpired,
+        /// Relayer has chain assignments, and the source chain isn't one of them
+        RelayerNotAssignedToChain,
     }
 }
 
@@ -181,24 +746,150 @@ decl_storage! {
         /// All whitelisted chains and their respective transaction counts
         ChainNonces get(fn chains): map hasher(opaque_blake2_256) ChainId => Option<DepositNonce>;
 
+        /// All whitelisted chains, as the keys of `ChainNonces` can't be iterated
+        pub ChainList get(fn chain_list): Vec<ChainId>;
+
+        /// Block of the most recent change to the chain whitelist
+        pub LastWhitelistChange get(fn last_whitelist_change): Option<T::BlockNumber>;
+
+        /// Human-readable names of chains, for display only
+        pub ChainNames get(fn chain_name): map hasher(opaque_blake2_256) ChainId => Option<Vec<u8>>;
+
+        /// Proposal lifetimes of chains that don't use the default lifetime
+        pub ChainProposalLifetimes get(fn chain_proposal_lifetime):
+            map hasher(opaque_blake2_256) ChainId => Option<T::BlockNumber>;
+
+        /// Proposal lifetime of chains without an override, in place of `ProposalLifetime`
+        pub StoredProposalLifetime get(fn stored_proposal_lifetime): Option<T::BlockNumber>;
+
         /// Number of votes required for a proposal to execute
         RelayerThreshold get(fn relayer_threshold): u32 = DEFAULT_RELAYER_THRESHOLD;
 
+        /// Number of votes, for or against, required before a proposal can be approved or rejected
+        pub Quorum get(fn quorum): u32;
+
         /// Tracks current relayer set
         pub Relayers get(fn relayers): map hasher(opaque_blake2_256) T::AccountId => bool;
 
+        /// Accounts allowed to call admin methods in addition to `AdminOrigin`
+        pub Admins get(fn admins): map hasher(opaque_blake2_256) T::AccountId => Option<()>;
+
         /// Number of relayers in set
         pub RelayerCount get(fn relayer_count): u32;
 
+        /// Members of the relayer set in the order they were added, since `Relayers` can't be
+        /// iterated. Relayers added before this list existed are missing until re-added.
+        pub RelayerList get(fn relayer_list): Vec<T::AccountId>;
+
+        /// Block from which each relayer added with `add_relayer` can vote
+        pub RelayerActiveFrom get(fn relayer_active_from):
+            map hasher(opaque_blake2_256) T::AccountId => Option<T::BlockNumber>;
+
+        /// Chains each relayer has been assigned to relay from. Relayers without any assignment
+        /// may vote on proposals from every chain.
+        pub RelayerChains get(fn relayer_chain_assigned):
+            double_map hasher(opaque_blake2_256) T::AccountId, hasher(opaque_blake2_256) ChainId => bool;
+
+        /// Block of the latest heartbeat sent by each relayer
+        pub LastHeartbeat get(fn last_heartbeat):
+            map hasher(opaque_blake2_256) T::AccountId => Option<T::BlockNumber>;
+
         /// All known proposals.
         /// The key is the hash of the call and the deposit ID, to ensure it's unique.
         pub Votes get(fn votes):
             double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) (DepositNonce, T::Proposal)
-            => Option<ProposalVotes<T::AccountId, T::BlockNumber>>;
-
-        /// Utilized by the bridge software to map resource IDs to actual methods
+            => Option<ProposalVotes<T::AccountId, T::BlockNumber, T::MaxRelayers>>;
+
+        /// Index of the proposals in `Votes` by source chain, since the keys of `Votes` are
+        /// hashed and can't be iterated
+        pub ProposalKeys get(fn proposal_keys):
+            double_map hasher(blake2_128_concat) ChainId, hasher(blake2_128_concat) (DepositNonce, T::Proposal)
+            => ();
+
+        /// Source event proofs attached by relayers to their votes in favour of a proposal
+        pub VoteProofs get(fn vote_proofs):
+            double_map hasher(opaque_blake2_256) ChainId,
+            hasher(opaque_blake2_256) (DepositNonce, T::Proposal, T::AccountId)
+            => Option<Vec<u8>>;
+
+        /// Inbound transfers that have been executed, keyed by source chain, nonce and resource,
+        /// so the same transfer can't be executed again under a different call
+        pub SeenTransfers get(fn transfer_seen):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) (DepositNonce, ResourceId)
+            => bool;
+
+        /// Outcome of every proposal that has been approved or rejected, kept after the
+        /// proposal's votes are removed so its nonce can't be reused
+        pub CompletedProposals get(fn proposal_outcome):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) DepositNonce
+            => Option<ProposalStatus>;
+
+        /// Number of proposals ever created, including those since removed
+        pub TotalProposals get(fn total_proposals): u64;
+
+        /// Number of proposals that have been created but not yet approved, rejected or removed
+        pub TotalActiveProposals get(fn total_active_proposals): u32;
+
+        /// Number of proposal calls currently being dispatched
+        BridgeCallDepth get(fn bridge_call_depth): u8;
+
+        /// Utilized by the bridge software to map resource IDs to actual methods,
+        /// along with whether the resource is currently enabled
         pub Resources get(fn resources):
-            map hasher(opaque_blake2_256) ResourceId => Option<Vec<u8>>
+            map hasher(opaque_blake2_256) ResourceId => Option<(Vec<u8>, bool)>;
+
+        /// Minimum number of votes required for proposals of a resource, where higher than the
+        /// relayer threshold
+        pub ResourceThresholds get(fn resource_threshold):
+            map hasher(opaque_blake2_256) ResourceId => Option<u32>;
+
+        /// Number of registered resources, including disabled ones
+        pub ResourceCount get(fn resource_count): u32;
+
+        /// All registered resources, as the keys of `Resources` can't be iterated
+        pub ResourceList get(fn resource_list): Vec<ResourceId>;
+
+        /// Number of outbound transfers made for each resource ID, across all chains
+        pub ResourceTransferCount get(fn resource_transfer_count):
+            map hasher(opaque_blake2_256) ResourceId => u64;
+
+        /// Sequence number of the latest outbound transfer, across all chains and resources
+        pub GlobalTransferSeq get(fn transfer_seq): u64;
+
+        /// The latest outbound transfers, oldest first (dest_id, nonce, resource_id)
+        pub RecentTransfers get(fn recent_transfers):
+            BoundedVec<(ChainId, DepositNonce, ResourceId), T::MaxRecentTransfers>;
+
+        /// Details of the transfers in `RecentTransfers`, so their events can be emitted again
+        RecentTransferEvents get(fn recent_transfer_event):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) DepositNonce
+            => Option<TransferRecord<T::AccountId>>;
+
+        /// Number of outbound transfers made in the current block, cleared on finalization
+        TransfersThisBlock: u32;
+
+        /// Highest nonce of a successfully executed proposal for each source chain
+        pub LastApproved get(fn last_approved):
+            map hasher(opaque_blake2_256) ChainId => Option<DepositNonce>;
+
+        /// Protocol version last announced with a `BridgeVersion` event
+        AnnouncedVersion: Option<u32>;
+
+        /// Whether transfers and proposals are currently halted
+        pub IsPaused get(fn is_paused): bool;
+
+        /// Chains for which transfers and proposals are currently halted
+        pub PausedChains get(fn paused_chains): map hasher(opaque_blake2_256) ChainId => Option<()>;
+
+        /// Resources for which transfers and proposals are currently halted
+        pub PausedResources get(fn paused_resources):
+            map hasher(opaque_blake2_256) ResourceId => Option<()>;
+
+        /// Reply nonces of outbound generic transfers still awaiting a reply,
+        /// keyed by destination chain and outbound nonce
+        pub PendingReplies get(fn pending_replies):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) DepositNonce
+            => Option<DepositNonce>;
     }
 }
 
@@ -209,9 +900,55 @@ decl_module! {
         const ChainIdentity: ChainId = T::ChainId::get();
         const ProposalLifetime: T::BlockNumber = T::ProposalLifetime::get();
         const BridgeAccountId: T::AccountId = MODULE_ID.into_account();
+        const MaxRelayers: u32 = T::MaxRelayers::get();
+        const MaxFungibleMetadataLen: u32 = T::MaxFungibleMetadataLen::get();
+        const MaxFungibleRecipients: u32 = T::MaxFungibleRecipients::get();
+        const MaxBatchSize: u32 = T::MaxBatchSize::get();
+        const MaxTokenIdLen: u32 = T::MaxTokenIdLen::get();
+        const MaxNftMetadataLen: u32 = T::MaxNftMetadataLen::get();
+        const MaxProposalNoteLen: u32 = T::MaxProposalNoteLen::get();
+        const MaxVoteProofLen: u32 = T::MaxVoteProofLen::get();
+        const MaxChainNameLen: u32 = T::MaxChainNameLen::get();
+        const MaxRecentTransfers: u32 = T::MaxRecentTransfers::get();
+        const MaxTotalActiveProposals: u32 = T::MaxTotalActiveProposals::get();
+        const NonceStep: DepositNonce = T::NonceStep::get();
+        const MinConfirmations: T::BlockNumber = T::MinConfirmations::get();
+        const TransferVolumeAlertThreshold: u32 = T::TransferVolumeAlertThreshold::get();
+
+        const BridgeVersion: u32 = BRIDGE_VERSION;
 
         fn deposit_event() = default;
 
+        // Events can't be deposited while building genesis, so the version is announced in the
+        // first block instead, and again whenever a runtime upgrade changes it.
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            if <AnnouncedVersion>::get() != Some(BRIDGE_VERSION) {
+                <AnnouncedVersion>::put(BRIDGE_VERSION);
+                Self::deposit_event(RawEvent::BridgeVersion(BRIDGE_VERSION));
+                T::DbWeight::get().reads_writes(1, 1)
+            } else {
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            match Self::storage_version() {
+                Some(version) if version >= PalletVersion::new(0, 0, 3) => 0,
+                _ => Self::migrate_to_v0_0_3(),
+            }
+        }
+
+        fn on_finalize(_n: T::BlockNumber) {
+            let transfers = <TransfersThisBlock>::take();
+            if transfers > T::TransferVolumeAlertThreshold::get() {
+                Self::deposit_event(RawEvent::HighTransferVolume(transfers));
+            }
+        }
+
+        fn integrity_test() {
+            assert!(T::NonceStep::get() > 0, "NonceStep must be greater than zero");
+        }
+
         /// Sets the vote threshold for proposals.
         ///
         /// This threshold is used to determine how many votes are required
@@ -226,6 +963,117 @@ decl_module! {
             Self::set_relayer_threshold(threshold)
         }
 
+        /// Sets the minimum vote threshold for proposals of a resource. Proposals use the higher of
+        /// this and the relayer threshold, so it can only raise the number of votes required.
+        /// A threshold of zero clears it.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_resource_threshold(origin, id: ResourceId, threshold: u32) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_resource_vote_threshold(id, threshold)
+        }
+
+        /// Sets the number of blocks proposals from a chain stay open for voting, in place of
+        /// the default lifetime. Proposals that already exist keep their expiry. A lifetime of zero
+        /// clears it.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_chain_proposal_lifetime(origin, id: ChainId, lifetime: T::BlockNumber) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_chain_lifetime(id, lifetime)
+        }
+
+        /// Sets the number of blocks proposals from chains without an override stay open for
+        /// voting, in place of `ProposalLifetime`. Proposals that already exist keep their expiry.
+        /// A lifetime of zero clears it.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_proposal_lifetime(origin, lifetime: T::BlockNumber) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_default_lifetime(lifetime)
+        }
+
+        /// Sets the number of votes, for or against, that must be cast before a proposal can be
+        /// approved or rejected. A quorum of zero leaves the decision to the threshold alone.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_quorum(origin, quorum: u32) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_vote_quorum(quorum)
+        }
+
+        /// Halts or resumes all transfers and proposals.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_paused(origin, paused: bool) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_pause_state(paused)
+        }
+
+        /// Halts or resumes transfers to and proposals from a single chain.
+        /// A global pause applies regardless of this setting.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_chain_paused(origin, id: ChainId, paused: bool) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_chain_pause_state(id, paused)
+        }
+
+        /// Halts or resumes transfers and proposals of a single resource.
+        /// A global or chain pause applies regardless of this setting.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn set_resource_paused(origin, id: ResourceId, paused: bool) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_resource_pause_state(id, paused)
+        }
+
+        /// Emits the event of a recent outbound transfer again, followed by `TransferReplayed`,
+        /// for relayers that missed it. Only transfers still in `RecentTransfers` can be replayed.
+        ///
+        /// # <weight>
+        /// - O(1) lookup
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn reemit_transfer(origin, dest_id: ChainId, nonce: DepositNonce) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::replay_transfer(dest_id, nonce)
+        }
+
+        /// Overwrites the global transfer sequence, for reconciliation after a fork or rollback.
+        ///
+        /// The next outbound transfer will use `value + 1`.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn reset_transfer_seq(origin, value: u64) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_transfer_seq(value)
+        }
+
         /// Stores a method name on chain under an associated resource ID.
         ///
         /// # <weight>
@@ -251,85 +1099,375 @@ decl_module! {
             Self::unregister_resource(id)
         }
 
-        /// Enables a chain ID as a source or destination for a bridge transfer.
+        /// Enables or disables a resource ID without removing its method.
+        ///
+        /// While disabled, bridge transfers and proposals with the resource ID will be rejected.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
         #[weight = 195_000_000]
-        pub fn whitelist_chain(origin, id: ChainId) -> DispatchResult {
+        pub fn set_resource_enabled(origin, id: ResourceId, enabled: bool) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::whitelist(id)
+            Self::toggle_resource(id, enabled)
         }
 
-        /// Adds a new relayer to the relayer set.
+        /// Enables a chain ID as a source or destination for a bridge transfer.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
         #[weight = 195_000_000]
-        pub fn add_relayer(origin, v: T::AccountId) -> DispatchResult {
+        pub fn whitelist_chain(origin, id: ChainId) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::register_relayer(v)
+            Self::whitelist(id)
         }
 
-        /// Removes an existing relayer from the set.
+        /// Enables a chain ID as a source or destination and registers its resources in a
+        /// single call.
+        ///
+        /// A single `ChainOnboarded` event is emitted, along with the events of each step
+        /// if `VerboseOnboarding` is set.
         ///
         /// # <weight>
-        /// - O(1) lookup and removal
+        /// - O(n) inserts, where n is the number of resources
         /// # </weight>
         #[weight = 195_000_000]
-        pub fn remove_relayer(origin, v: T::AccountId) -> DispatchResult {
+        pub fn onboard_chain(origin, id: ChainId, resources: Vec<(ResourceId, Vec<u8>)>) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::unregister_relayer(v)
+            Self::onboard(id, resources)
         }
 
-        /// Commits a vote in favour of the provided proposal.
+        /// Enables several chain IDs as sources or destinations, each starting at the given
+        /// deposit nonce.
         ///
-        /// If a proposal with the given nonce and source chain ID does not already exist, it will
-        /// be created with an initial vote in favour from the caller.
+        /// This is intended for chains being re-onboarded, where starting again at nonce 0
+        /// would allow old deposits to be replayed. No chain is whitelisted if any entry is invalid.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(n) lookups and inserts, where n is the number of entries
         /// # </weight>
-        #[weight = (call.get_dispatch_info().weight + 195_000_000, call.get_dispatch_info().class, Pays::Yes)]
-        pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
-            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
-            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
-
-            Self::vote_for(who, nonce, src_id, call)
+        #[weight = 195_000_000]
+        pub fn whitelist_chains_with_nonce(origin, entries: Vec<(ChainId, DepositNonce)>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::whitelist_with_nonces(entries)
         }
 
-        /// Commits a vote against a provided proposal.
+        /// Removes a chain ID from the whitelist, so no further transfers to or proposals from it
+        /// are accepted.
+        ///
+        /// The last deposit nonce is included in the event. The chain should be re-added with
+        /// `whitelist_chains_with_nonce` from that nonce to avoid old deposits being replayed.
         ///
         /// # <weight>
-        /// - Fixed, since execution of proposal should not be included
+        /// - O(1) removal
         /// # </weight>
         #[weight = 195_000_000]
-        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
-            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
-            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
-
-            Self::vote_against(who, nonce, src_id, call)
+        pub fn remove_chain(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::remove_whitelisted_chain(id)
         }
 
-        /// Evaluate the state of a proposal given the current vote threshold.
+        /// Sets the human-readable name of a chain, or clears it if `name` is empty.
         ///
-        /// A proposal with enough votes will be either executed or cancelled, and the status
-        /// will be updated accordingly.
+        /// The chain doesn't need to be whitelisted, so it can be named before it is added.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(1) write
         /// # </weight>
-        #[weight = (prop.get_dispatch_info().weight + 195_000_000, prop.get_dispatch_info().class, Pays::Yes)]
-        pub fn eval_vote_state(origin, nonce: DepositNonce, src_id: ChainId, prop: Box<<T as Config>::Proposal>) -> DispatchResult {
-            ensure_signed(origin)?;
-
-            Self::try_resolve_proposal(nonce, src_id, prop)
+        #[weight = 195_000_000]
+        pub fn set_chain_name(origin, id: ChainId, name: Vec<u8>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_name(id, name)
+        }
+
+        /// Adds a new relayer to the relayer set.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn add_relayer(origin, v: T::AccountId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::register_relayer(v)
+        }
+
+        /// Removes an existing relayer from the set.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn remove_relayer(origin, v: T::AccountId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::unregister_relayer(v)
+        }
+
+        /// Assigns a relayer to relay proposals from a chain. The chain is whitelisted as well
+        /// if `AutoWhitelistOnRelayerAssign` is set.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn assign_relayer_chain(origin, relayer: T::AccountId, id: ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::assign_chain(relayer, id)
+        }
+
+        /// Replaces the relayer set and threshold in a single call, so the bridge is never left
+        /// with a threshold the relayer set can't meet. In-flight proposals are kept.
+        ///
+        /// # <weight>
+        /// - O(n) lookups, removals and inserts, where n is the number of old and new relayers
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn rotate_committee(origin, relayers: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::rotate_relayers(relayers, threshold)
+        }
+
+        /// Replaces the relayer set and threshold, discarding all in-flight proposals.
+        ///
+        /// This is a recovery mechanism for a compromised or unusable relayer set, so it can only
+        /// be called by root, regardless of `AdminOrigin`.
+        ///
+        /// # <weight>
+        /// - O(n) removals and inserts, where n is the number of relayers and proposals
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn emergency_reset(origin, relayers: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::replace_relayer_set(relayers, threshold)
+        }
+
+        /// Restores the relayer set, whitelisted chains and resources from a snapshot taken with
+        /// `export_state`, replacing the current configuration and discarding all in-flight proposals.
+        ///
+        /// # <weight>
+        /// - O(n) removals and inserts, where n is the number of relayers, chains, resources and proposals
+        /// # </weight>
+        #[weight = T::WeightInfo::import_state(
+            snapshot.relayers.len() as u32,
+            snapshot.chains.len() as u32,
+            snapshot.resources.len() as u32,
+        )]
+        pub fn import_state(origin, snapshot: BridgeStateSnapshot<T::AccountId>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::restore_state(snapshot)
+        }
+
+        /// Allows an account to call admin methods, alongside `AdminOrigin`.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn add_admin(origin, who: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::register_admin(who)
+        }
+
+        /// Removes an account added with `add_admin`.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn remove_admin(origin, who: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::unregister_admin(who)
+        }
+
+        /// Removes up to `limit` proposals from `src_id` whose stored votes can no longer be
+        /// decoded, for example after a runtime upgrade changed the encoding of `Proposal`.
+        ///
+        /// # <weight>
+        /// - O(n) lookups, where n is the number of proposals from `src_id`
+        /// - O(limit) removals
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn drop_undecodable_votes(origin, src_id: ChainId, limit: u32) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            let removed = Self::remove_undecodable_votes(src_id, limit);
+            Self::deposit_event(RawEvent::UndecodableVotesDropped(src_id, removed));
+            Ok(())
+        }
+
+        /// Creates a proposal without voting on it.
+        ///
+        /// This allows a relayer to propose a transfer without also acting as a voter. Votes are
+        /// then submitted with `acknowledge_proposal` and `reject_proposal` as usual.
+        ///
+        /// A short note can be attached for operators, such as a reference to the source transaction.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn create_proposal(origin, nonce: DepositNonce, src_id: SrcChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>, note: Vec<u8>) -> DispatchResult {
+            let src_id = src_id.0;
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
+            ensure!(note.len() <= T::MaxProposalNoteLen::get() as usize, Error::<T>::NoteTooLong);
+            ensure!(T::ProposalFilter::filter(&call), Error::<T>::ProposalFiltered);
+
+            Self::create(nonce, src_id, r_id, call, note)
+        }
+
+        /// Commits a vote in favour of the provided proposal.
+        ///
+        /// If a proposal with the given nonce and source chain ID does not already exist, it will
+        /// be created with an initial vote in favour from the caller. Otherwise the vote is added
+        /// to the existing proposal, including one made with `create_proposal`. Unlike
+        /// `create_proposal` this never fails with `ProposalAlreadyExists`.
+        ///
+        /// A `proof` of the observed source event can be attached and is recorded with the vote.
+        /// An empty proof is treated as absent, which is rejected if `RequireVoteProof` is set.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(c) storage writes, where c is the encoded length of the proposed call
+        /// # </weight>
+        #[weight = (call.get_dispatch_info().weight + T::WeightInfo::acknowledge_proposal(call.encoded_size() as u32), call.get_dispatch_info().class, Pays::Yes)]
+        pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: SrcChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>, proof: Vec<u8>) -> DispatchResultWithPostInfo {
+            let call_weight = call.get_dispatch_info().weight;
+            let weight = T::WeightInfo::acknowledge_proposal(call.encoded_size() as u32);
+            let src_id = src_id.0;
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
+            ensure!(!proof.is_empty() || !T::RequireVoteProof::get(), Error::<T>::MissingProof);
+            ensure!(T::ProposalFilter::filter(&call), Error::<T>::ProposalFiltered);
+            ensure!(
+                !T::RejectStaleNonces::get() || !Self::is_stale_nonce(src_id, nonce),
+                Error::<T>::NonceAlreadyUsed
+            );
+            ensure!(proof.len() <= T::MaxVoteProofLen::get() as usize, Error::<T>::ProofTooLong);
+
+            let executed = Self::vote_for(who.clone(), nonce, src_id, r_id, call.clone())?;
+            if !proof.is_empty() {
+                <VoteProofs<T>>::insert(src_id, (nonce, *call, who), proof);
+            }
+            Ok(Some(weight.saturating_add(executed.unwrap_or(call_weight))).into())
+        }
+
+        /// Commits a vote against a provided proposal.
+        ///
+        /// Creates the proposal if it doesn't exist, unless `AllowRejectCreatesProposal` is disabled.
+        ///
+        /// # <weight>
+        /// - Fixed, since execution of proposal should not be included
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: SrcChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResultWithPostInfo {
+            let src_id = src_id.0;
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
+            ensure!(
+                T::AllowRejectCreatesProposal::get()
+                    || <Votes<T>>::contains_key(src_id, (nonce, (*call).clone())),
+                Error::<T>::ProposalDoesNotExist
+            );
+
+            let executed = Self::vote_against(who, nonce, src_id, r_id, call)?;
+            Ok(Some((195_000_000 as Weight).saturating_add(executed.unwrap_or(0))).into())
+        }
+
+        /// Evaluate the state of a proposal given the current vote threshold.
+        ///
+        /// A proposal with enough votes will be either executed or cancelled, and the status
+        /// will be updated accordingly.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, regardless of whether execution is performed
+        /// # </weight>
+        #[weight = (prop.get_dispatch_info().weight + 195_000_000, prop.get_dispatch_info().class, Pays::Yes)]
+        pub fn eval_vote_state(origin, nonce: DepositNonce, src_id: SrcChainId, prop: Box<<T as Config>::Proposal>) -> DispatchResultWithPostInfo {
+            let call_weight = prop.get_dispatch_info().weight;
+            let src_id = src_id.0;
+            ensure_signed(origin)?;
+
+            let executed = Self::try_resolve_proposal(nonce, src_id, prop)?;
+            Ok(Some((195_000_000 as Weight).saturating_add(executed.unwrap_or(call_weight))).into())
+        }
+
+        /// Signals that the calling relayer is online, even if it has nothing to vote on.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn heartbeat(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+
+            let now = <frame_system::Module<T>>::block_number();
+            <LastHeartbeat<T>>::insert(&who, now);
+            Self::deposit_event(RawEvent::RelayerHeartbeat(who, now));
+            Ok(())
+        }
+
+        /// Rejects a proposal immediately, even if it has enough votes in favour to be approved.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn force_reject(origin, src_id: SrcChainId, nonce: DepositNonce, call: Box<<T as Config>::Proposal>) -> DispatchResult {
+            T::VetoOrigin::ensure_origin(origin)?;
+
+            Self::veto_proposal(src_id.0, nonce, call)
+        }
+
+        /// Removes a proposal that expired before a decision was reached, rather than leaving
+        /// it in storage. Its nonce is recorded as rejected so it can't be proposed again.
+        ///
+        /// # <weight>
+        /// - O(n) removals, where n is the number of votes in favour
+        /// # </weight>
+        #[weight = T::WeightInfo::expire_proposal()]
+        pub fn expire_proposal(origin, src_id: SrcChainId, nonce: DepositNonce, call: Box<<T as Config>::Proposal>) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::remove_expired_proposal(src_id.0, nonce, call)
+        }
+
+        /// Initiates a fungible transfer out of the chain. See `Module::transfer_fungible`.
+        ///
+        /// # <weight>
+        /// - O(1) lookups and inserts
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn submit_fungible_transfer(origin, dest_id: ChainId, resource_id: ResourceId, to: Vec<u8>, amount: U256, metadata: Vec<u8>) -> DispatchResult {
+            T::TransferOrigin::ensure_origin(origin)?;
+            Self::transfer_fungible(dest_id, resource_id, to, amount, metadata, None)
+        }
+
+        /// Initiates a nonfungible transfer out of the chain. See `Module::transfer_nonfungible`.
+        ///
+        /// # <weight>
+        /// - O(1) lookups and inserts
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn submit_nonfungible_transfer(origin, dest_id: ChainId, resource_id: ResourceId, token_id: Vec<u8>, to: Vec<u8>, metadata: Vec<u8>) -> DispatchResult {
+            T::TransferOrigin::ensure_origin(origin)?;
+            Self::transfer_nonfungible(dest_id, resource_id, token_id, to, metadata, None)
+        }
+
+        /// Initiates a generic transfer out of the chain. See `Module::transfer_generic`.
+        ///
+        /// # <weight>
+        /// - O(1) lookups and inserts
+        /// # </weight>
+        #[weight = 195_000_000]
+        pub fn submit_generic_transfer(origin, dest_id: ChainId, resource_id: ResourceId, metadata: Vec<u8>) -> DispatchResult {
+            T::TransferOrigin::ensure_origin(origin)?;
+            Self::transfer_generic(dest_id, resource_id, metadata, None)
         }
     }
 }
@@ -340,10 +1478,23 @@ impl<T: Config> Module<T> {
     pub fn ensure_admin(o: T::Origin) -> DispatchResult {
         T::AdminOrigin::try_origin(o)
             .map(|_| ())
+            .or_else(|o| EnsureAdminMember::<T>::try_origin(o).map(|_| ()))
             .or_else(ensure_root)?;
         Ok(())
     }
 
+    /// Checks if who is an admin member
+    pub fn is_admin(who: &T::AccountId) -> bool {
+        <Admins<T>>::contains_key(who)
+    }
+
+    /// Checks that a resource belongs either to the given chain or to this chain, the only
+    /// two chains a transfer between them can be for
+    fn resource_for_chain(r_id: ResourceId, chain: ChainId) -> bool {
+        T::ResourceIdScheme::matches_chain(r_id, chain)
+            || T::ResourceIdScheme::matches_chain(r_id, T::ChainId::get())
+    }
+
     /// Checks if who is a relayer
     pub fn is_relayer(who: &T::AccountId) -> bool {
         Self::relayers(who)
@@ -355,9 +1506,162 @@ impl<T: Config> Module<T> {
         MODULE_ID.into_account()
     }
 
-    /// Asserts if a resource is registered
+    /// Provides the AccountId that bridge fees are collected into, kept separate from
+    /// `account_id` so fees aren't mixed with funds held for transfers.
+    pub fn fee_account_id() -> T::AccountId {
+        FEE_MODULE_ID.into_account()
+    }
+
+    /// Asserts if a resource is registered and enabled
     pub fn resource_exists(id: ResourceId) -> bool {
-        return Self::resources(id) != None;
+        matches!(Self::resources(id), Some((_, true)))
+    }
+
+    /// Checks if a resource is registered but has been disabled
+    pub fn resource_disabled(id: ResourceId) -> bool {
+        matches!(Self::resources(id), Some((_, false)))
+    }
+
+    /// Returns the full storage key of the `Votes` entry for a proposal, so that off-chain
+    /// tooling can query or subscribe to it directly.
+    #[cfg(feature = "std")]
+    pub fn votes_storage_key(src_id: ChainId, nonce: DepositNonce, call: &T::Proposal) -> Vec<u8> {
+        use sp_io::hashing::{blake2_256, twox_128};
+
+        let mut key = twox_128(b"ChainBridge").to_vec();
+        key.extend_from_slice(&twox_128(b"Votes"));
+        key.extend_from_slice(&blake2_256(&src_id.encode()));
+        key.extend_from_slice(&blake2_256(&(nonce, call).encode()));
+        key
+    }
+
+    /// Version of the bridge protocol implemented by this pallet
+    pub fn bridge_version() -> u32 {
+        BRIDGE_VERSION
+    }
+
+    /// Checks if proposals can currently be finalized: the bridge must not be paused and there
+    /// must be enough relayers to reach a non-zero threshold.
+    pub fn can_finalize() -> bool {
+        let threshold = Self::relayer_threshold();
+        !Self::is_paused() && threshold > 0 && Self::relayer_count() >= threshold
+    }
+
+    /// Number of votes required to approve a proposal of the given resource
+    pub fn proposal_threshold(resource_id: ResourceId) -> u32 {
+        Self::relayer_threshold().max(Self::resource_threshold(resource_id).unwrap_or(0))
+    }
+
+    /// Whether `voters` would meet `threshold`, counting each current relayer among them once.
+    /// Duplicates and accounts that aren't relayers are ignored.
+    pub fn would_approve(voters: &[T::AccountId], threshold: u32) -> bool {
+        let valid = voters
+            .iter()
+            .enumerate()
+            .filter(|(i, who)| Self::is_relayer(who) && !voters[..*i].contains(who))
+            .count();
+        valid as u32 >= threshold
+    }
+
+    /// Number of blocks a proposal from `src_id` stays open for voting after it is created.
+    /// This is the chain's override if one is set, then the lifetime set with
+    /// `set_proposal_lifetime`, and `ProposalLifetime` otherwise.
+    pub fn effective_lifetime(src_id: ChainId) -> T::BlockNumber {
+        Self::chain_proposal_lifetime(src_id)
+            .or_else(Self::stored_proposal_lifetime)
+            .unwrap_or_else(T::ProposalLifetime::get)
+    }
+
+    /// Block at which a proposal was first created, if it is known
+    pub fn proposal_created_at(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop: T::Proposal,
+    ) -> Option<T::BlockNumber> {
+        Self::votes(src_id, (nonce, prop)).map(|votes| votes.created_at)
+    }
+
+    /// Nonces of the proposals from `src_id` that currently have the given status, in ascending
+    /// order. Proposals that have been removed are not included, but their outcome may still be
+    /// found in `CompletedProposals`.
+    pub fn proposals_by_status(src_id: ChainId, status: ProposalStatus) -> Vec<DepositNonce> {
+        let mut nonces: Vec<DepositNonce> = <ProposalKeys<T>>::iter_prefix(src_id)
+            .filter_map(|(key, _)| {
+                let nonce = key.0;
+                Self::votes(src_id, key)
+                    .filter(|votes| votes.status == status)
+                    .map(|_| nonce)
+            })
+            .collect();
+        nonces.sort();
+        nonces
+    }
+
+    /// Checks if `call` matches the method registered for the resource, in the form
+    /// `Pallet.function`. Resources without a registered method match any call.
+    pub fn resource_method_matches(id: ResourceId, call: &T::Proposal) -> bool {
+        match Self::resources(id) {
+            Some((method, _)) => {
+                let metadata = call.get_call_metadata();
+                method
+                    == [
+                        metadata.pallet_name.as_bytes(),
+                        b".",
+                        metadata.function_name.as_bytes(),
+                    ]
+                    .concat()
+            }
+            None => true,
+        }
+    }
+
+    /// Checks if `who` may vote on proposals from `src_id`, which is the case if the relayer is
+    /// assigned to the chain or has no chain assignments at all
+    pub fn relayer_may_vote_from(who: &T::AccountId, src_id: ChainId) -> bool {
+        Self::relayer_chain_assigned(who, src_id)
+            || <RelayerChains<T>>::iter_prefix_values(who).next().is_none()
+    }
+
+    /// Checks if `nonce` is at or below the highest nonce executed for `src_id`
+    pub fn is_stale_nonce(src_id: ChainId, nonce: DepositNonce) -> bool {
+        match Self::last_approved(src_id) {
+            Some(last) => nonce <= last,
+            None => false,
+        }
+    }
+
+    /// Hash of a proposal's call, as included in `ProposalCreated`
+    pub fn proposal_hash(prop: &T::Proposal) -> T::Hash {
+        T::Hashing::hash_of(prop)
+    }
+
+    /// Hash of the current relayer set, independent of the order in which relayers were added.
+    /// Counterpart chains can compare it against their own view of the set.
+    pub fn relayer_set_hash() -> T::Hash {
+        let mut relayers = Self::relayer_list();
+        relayers.sort();
+        T::Hashing::hash_of(&relayers)
+    }
+
+    /// Each relayer with the block of its latest heartbeat, if it has sent one
+    pub fn relayers_status() -> Vec<(T::AccountId, Option<T::BlockNumber>)> {
+        Self::relayer_list()
+            .into_iter()
+            .map(|relayer| {
+                let heartbeat = Self::last_heartbeat(&relayer);
+                (relayer, heartbeat)
+            })
+            .collect()
+    }
+
+    /// Checks if transfers to and proposals from a chain are halted
+    pub fn is_chain_paused(id: ChainId) -> bool {
+        <PausedChains>::contains_key(id)
+    }
+
+    /// Checks if transfers and proposals of a resource are halted
+    pub fn is_resource_paused(id: ResourceId) -> bool {
+        <PausedResources>::contains_key(id)
     }
 
     /// Checks if a chain exists as a whitelisted destination
@@ -365,46 +1669,369 @@ impl<T: Config> Module<T> {
         return Self::chains(id) != None;
     }
 
-    /// Increments the deposit nonce for the specified chain ID
+    /// Increments the deposit nonce for the specified chain ID by `NonceStep`
     fn bump_nonce(id: ChainId) -> DepositNonce {
-        let nonce = Self::chains(id).unwrap_or_default() + 1;
+        let nonce = Self::chains(id).unwrap_or_default() + T::NonceStep::get();
         <ChainNonces>::insert(id, nonce);
         nonce
     }
 
+    /// Checks that an outbound transfer to `dest_id` of `resource_id` is currently permitted
+    fn ensure_can_transfer(dest_id: ChainId, resource_id: ResourceId) -> DispatchResult {
+        ensure!(!Self::is_paused(), Error::<T>::BridgeIsPaused);
+        ensure!(!Self::is_chain_paused(dest_id), Error::<T>::ChainPaused);
+        ensure!(
+            Self::chain_whitelisted(dest_id),
+            Error::<T>::ChainNotWhitelisted
+        );
+        ensure!(
+            !Self::resource_disabled(resource_id),
+            Error::<T>::ResourceDisabled
+        );
+        ensure!(
+            !Self::is_resource_paused(resource_id),
+            Error::<T>::ResourcePaused
+        );
+        ensure!(
+            Self::bridge_call_depth() <= T::MaxBridgeDepth::get(),
+            Error::<T>::MaxDepthExceeded
+        );
+        Ok(())
+    }
+
+    /// Updates the bookkeeping for an outbound transfer of the given resource
+    fn record_transfer(dest_id: ChainId, nonce: DepositNonce, resource_id: ResourceId) {
+        <ResourceTransferCount>::mutate(resource_id, |count| *count = count.saturating_add(1));
+        <GlobalTransferSeq>::mutate(|seq| *seq = seq.saturating_add(1));
+        <TransfersThisBlock>::mutate(|count| *count = count.saturating_add(1));
+
+        let mut recent = <RecentTransfers<T>>::take().into_inner();
+        recent.push((dest_id, nonce, resource_id));
+        let excess = recent
+            .len()
+            .saturating_sub(T::MaxRecentTransfers::get() as usize);
+        for (dest_id, nonce, _) in recent.drain(..excess) {
+            <RecentTransferEvents<T>>::remove(dest_id, nonce);
+        }
+        if let Ok(recent) = BoundedVec::try_from(recent) {
+            <RecentTransfers<T>>::put(recent);
+        }
+    }
+
+    /// Deposits the event of an outbound transfer, keeping its details while it is a recent
+    /// transfer
+    fn deposit_transfer_event(
+        dest_id: ChainId,
+        nonce: DepositNonce,
+        record: TransferRecord<T::AccountId>,
+    ) {
+        if T::MaxRecentTransfers::get() > 0 {
+            <RecentTransferEvents<T>>::insert(dest_id, nonce, record.clone());
+        }
+        Self::deposit_event(Self::transfer_event(dest_id, nonce, record));
+    }
+
+    /// Event of the outbound transfer to `dest_id` with `nonce`
+    fn transfer_event(
+        dest_id: ChainId,
+        nonce: DepositNonce,
+        record: TransferRecord<T::AccountId>,
+    ) -> Event<T> {
+        match record {
+            TransferRecord::Fungible(resource_id, amount, to, metadata, source) => {
+                RawEvent::FungibleTransfer(
+                    dest_id,
+                    nonce,
+                    resource_id,
+                    amount,
+                    to,
+                    metadata,
+                    source,
+                )
+            }
+            TransferRecord::NonFungible(resource_id, token_id, to, metadata, source) => {
+                RawEvent::NonFungibleTransfer(
+                    dest_id,
+                    nonce,
+                    resource_id,
+                    token_id,
+                    to,
+                    metadata,
+                    source,
+                )
+            }
+            TransferRecord::Generic(resource_id, metadata, source) => {
+                RawEvent::GenericTransfer(dest_id, nonce, resource_id, metadata, source)
+            }
+            TransferRecord::GenericWithReply(resource_id, metadata, reply_nonce) => {
+                RawEvent::GenericTransferWithReply(
+                    dest_id,
+                    nonce,
+                    resource_id,
+                    metadata,
+                    reply_nonce,
+                )
+            }
+        }
+    }
+
     // *** Admin methods ***
 
-    /// Set a new voting threshold
-    pub fn set_relayer_threshold(threshold: u32) -> DispatchResult {
-        ensure!(threshold > 0, Error::<T>::InvalidThreshold);
-        <RelayerThreshold>::put(threshold);
-        Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+    /// Set a new voting threshold
+    pub fn set_relayer_threshold(threshold: u32) -> DispatchResult {
+        ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+        <RelayerThreshold>::put(threshold);
+        debug::debug!(target: LOG_TARGET, "Relayer threshold changed to {}", threshold);
+        Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+        Ok(())
+    }
+
+    /// Set or clear the minimum voting threshold of a resource
+    pub fn set_resource_vote_threshold(id: ResourceId, threshold: u32) -> DispatchResult {
+        if threshold == 0 {
+            <ResourceThresholds>::remove(id);
+        } else {
+            <ResourceThresholds>::insert(id, threshold);
+        }
+        Self::deposit_event(RawEvent::ResourceThresholdChanged(id, threshold));
+        Ok(())
+    }
+
+    /// Set or clear the proposal lifetime override of a chain
+    pub fn set_chain_lifetime(id: ChainId, lifetime: T::BlockNumber) -> DispatchResult {
+        if lifetime.is_zero() {
+            <ChainProposalLifetimes<T>>::remove(id);
+        } else {
+            <ChainProposalLifetimes<T>>::insert(id, lifetime);
+        }
+        Self::deposit_event(RawEvent::ChainProposalLifetimeChanged(id, lifetime));
+        Ok(())
+    }
+
+    /// Set or clear the proposal lifetime of chains without an override
+    pub fn set_default_lifetime(lifetime: T::BlockNumber) -> DispatchResult {
+        if lifetime.is_zero() {
+            <StoredProposalLifetime<T>>::kill();
+        } else {
+            <StoredProposalLifetime<T>>::put(lifetime);
+        }
+        Self::deposit_event(RawEvent::ProposalLifetimeChanged(lifetime));
+        Ok(())
+    }
+
+    /// Set a new voting quorum
+    pub fn set_vote_quorum(quorum: u32) -> DispatchResult {
+        <Quorum>::put(quorum);
+        Self::deposit_event(RawEvent::QuorumChanged(quorum));
+        Ok(())
+    }
+
+    /// Halt or resume all transfers and proposals
+    pub fn set_pause_state(paused: bool) -> DispatchResult {
+        <IsPaused>::put(paused);
+        if paused {
+            Self::deposit_event(RawEvent::BridgePaused);
+        } else {
+            Self::deposit_event(RawEvent::BridgeUnpaused);
+        }
+        Ok(())
+    }
+
+    /// Halt or resume transfers to and proposals from a chain
+    pub fn set_chain_pause_state(id: ChainId, paused: bool) -> DispatchResult {
+        if paused {
+            <PausedChains>::insert(id, ());
+            Self::deposit_event(RawEvent::ChainPaused(id));
+        } else {
+            <PausedChains>::remove(id);
+            Self::deposit_event(RawEvent::ChainUnpaused(id));
+        }
+        Ok(())
+    }
+
+    /// Halt or resume transfers and proposals of a resource
+    pub fn set_resource_pause_state(id: ResourceId, paused: bool) -> DispatchResult {
+        if paused {
+            <PausedResources>::insert(id, ());
+            Self::deposit_event(RawEvent::ResourcePaused(id));
+        } else {
+            <PausedResources>::remove(id);
+            Self::deposit_event(RawEvent::ResourceUnpaused(id));
+        }
+        Ok(())
+    }
+
+    /// Emit the event of a recent outbound transfer again
+    pub fn replay_transfer(dest_id: ChainId, nonce: DepositNonce) -> DispatchResult {
+        let record =
+            <RecentTransferEvents<T>>::get(dest_id, nonce).ok_or(Error::<T>::TransferNotFound)?;
+        Self::deposit_event(Self::transfer_event(dest_id, nonce, record));
+        Self::deposit_event(RawEvent::TransferReplayed(dest_id, nonce));
+        Ok(())
+    }
+
+    /// Overwrite the global transfer sequence
+    pub fn set_transfer_seq(value: u64) -> DispatchResult {
+        <GlobalTransferSeq>::put(value);
+        Self::deposit_event(RawEvent::TransferSeqReset(value));
+        Ok(())
+    }
+
+    /// Register a method for a resource Id, enabling associated transfers
+    pub fn register_resource(id: ResourceId, method: Vec<u8>) -> DispatchResult {
+        ensure!(
+            Self::chain_list()
+                .into_iter()
+                .any(|chain| T::ResourceIdScheme::matches_chain(id, chain))
+                || T::ResourceIdScheme::matches_chain(id, T::ChainId::get()),
+            Error::<T>::ResourceChainMismatch
+        );
+        if !<Resources>::contains_key(id) {
+            <ResourceCount>::mutate(|i| *i += 1);
+            <ResourceList>::append(id);
+        }
+        <Resources>::insert(id, (method, true));
+        Ok(())
+    }
+
+    /// Enables or disables a registered resource Id, keeping its method
+    pub fn toggle_resource(id: ResourceId, enabled: bool) -> DispatchResult {
+        <Resources>::try_mutate(id, |resource| match resource {
+            Some((_, flag)) => {
+                *flag = enabled;
+                Ok(())
+            }
+            None => Err(Error::<T>::ResourceDoesNotExist.into()),
+        })
+    }
+
+    /// Removes a resource ID, disabling associated transfer
+    pub fn unregister_resource(id: ResourceId) -> DispatchResult {
+        if <Resources>::take(id).is_some() {
+            <ResourceCount>::mutate(|i| *i -= 1);
+            <ResourceList>::mutate(|list| list.retain(|r| r != &id));
+        }
+        Ok(())
+    }
+
+    /// Whitelist a chain ID for transfer
+    pub fn whitelist(id: ChainId) -> DispatchResult {
+        // Cannot whitelist this chain
+        ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
+        // Cannot whitelist with an existing entry
+        ensure!(
+            !Self::chain_whitelisted(id),
+            Error::<T>::ChainAlreadyWhitelisted
+        );
+        Self::ensure_whitelist_cooldown()?;
+        Self::add_chain(id, 0, true);
+        Ok(())
+    }
+
+    /// Whitelist a chain ID for transfer and register its resources
+    pub fn onboard(id: ChainId, resources: Vec<(ResourceId, Vec<u8>)>) -> DispatchResult {
+        Self::ensure_batch_size(resources.len())?;
+        ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
+        ensure!(
+            !Self::chain_whitelisted(id),
+            Error::<T>::ChainAlreadyWhitelisted
+        );
+        ensure!(
+            resources
+                .iter()
+                .all(|(r_id, _)| Self::resource_for_chain(*r_id, id)),
+            Error::<T>::ResourceChainMismatch
+        );
+        Self::ensure_whitelist_cooldown()?;
+        Self::add_chain(id, 0, T::VerboseOnboarding::get());
+        let count = resources.len() as u32;
+        for (r_id, method) in resources {
+            Self::register_resource(r_id, method)?;
+        }
+        Self::deposit_event(RawEvent::ChainOnboarded(id, count));
+        Ok(())
+    }
+
+    /// Adds a chain to the whitelist starting at `nonce`, without any checks
+    fn add_chain(id: ChainId, nonce: DepositNonce, emit_event: bool) {
+        <LastWhitelistChange<T>>::put(<frame_system::Module<T>>::block_number());
+        <ChainNonces>::insert(&id, nonce);
+        <ChainList>::append(id);
+        if emit_event {
+            Self::deposit_event(RawEvent::ChainWhitelisted(id, Self::chain_name(id)));
+        }
+        T::OnChainWhitelisted::on_chain_whitelisted(id);
+    }
+
+    /// Whitelist several chain IDs for transfer, each starting at the provided nonce
+    pub fn whitelist_with_nonces(entries: Vec<(ChainId, DepositNonce)>) -> DispatchResult {
+        Self::ensure_batch_size(entries.len())?;
+        for (i, (id, _)) in entries.iter().enumerate() {
+            ensure!(*id != T::ChainId::get(), Error::<T>::InvalidChainId);
+            ensure!(
+                !Self::chain_whitelisted(*id) && !entries[..i].iter().any(|(prev, _)| prev == id),
+                Error::<T>::ChainAlreadyWhitelisted
+            );
+        }
+        Self::ensure_whitelist_cooldown()?;
+        for (id, nonce) in entries {
+            Self::add_chain(id, nonce, true);
+        }
+        Ok(())
+    }
+
+    /// Remove a chain ID from the whitelist
+    pub fn remove_whitelisted_chain(id: ChainId) -> DispatchResult {
+        ensure!(Self::chain_whitelisted(id), Error::<T>::ChainNotWhitelisted);
+        Self::ensure_whitelist_cooldown()?;
+        Self::drop_chain(id, true);
         Ok(())
     }
 
-    /// Register a method for a resource Id, enabling associated transfers
-    pub fn register_resource(id: ResourceId, method: Vec<u8>) -> DispatchResult {
-        <Resources>::insert(id, method);
+    /// Removes a chain from the whitelist, without any checks
+    fn drop_chain(id: ChainId, emit_event: bool) {
+        <LastWhitelistChange<T>>::put(<frame_system::Module<T>>::block_number());
+        let nonce = <ChainNonces>::take(id).unwrap_or_default();
+        <ChainList>::mutate(|list| list.retain(|c| *c != id));
+        if emit_event {
+            Self::deposit_event(RawEvent::ChainRemoved(id, nonce));
+        }
+        T::OnChainRemoved::on_chain_removed(id);
+    }
+
+    /// Checks that a batch input of `len` entries is within `MaxBatchSize`
+    pub fn ensure_batch_size(len: usize) -> DispatchResult {
+        ensure!(
+            len <= T::MaxBatchSize::get() as usize,
+            Error::<T>::BatchTooLarge
+        );
         Ok(())
     }
 
-    /// Removes a resource ID, disabling associated transfer
-    pub fn unregister_resource(id: ResourceId) -> DispatchResult {
-        <Resources>::remove(id);
+    /// Checks that `WhitelistChangeCooldown` has passed since the last whitelist change
+    fn ensure_whitelist_cooldown() -> DispatchResult {
+        if let Some(last) = Self::last_whitelist_change() {
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(
+                now >= last + T::WhitelistChangeCooldown::get(),
+                Error::<T>::WhitelistChangeTooSoon
+            );
+        }
         Ok(())
     }
 
-    /// Whitelist a chain ID for transfer
-    pub fn whitelist(id: ChainId) -> DispatchResult {
-        // Cannot whitelist this chain
-        ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
-        // Cannot whitelist with an existing entry
+    /// Set or clear the human-readable name of a chain
+    pub fn set_name(id: ChainId, name: Vec<u8>) -> DispatchResult {
         ensure!(
-            !Self::chain_whitelisted(id),
-            Error::<T>::ChainAlreadyWhitelisted
+            name.len() <= T::MaxChainNameLen::get() as usize,
+            Error::<T>::NameTooLong
         );
-        <ChainNonces>::insert(&id, 0);
-        Self::deposit_event(RawEvent::ChainWhitelisted(id));
+        if name.is_empty() {
+            <ChainNames>::remove(id);
+        } else {
+            <ChainNames>::insert(id, name.clone());
+        }
+        Self::deposit_event(RawEvent::ChainNameSet(id, name));
         Ok(())
     }
 
@@ -414,10 +2041,35 @@ impl<T: Config> Module<T> {
             !Self::is_relayer(&relayer),
             Error::<T>::RelayerAlreadyExists
         );
+        ensure!(
+            Self::relayer_count() < T::MaxRelayers::get(),
+            Error::<T>::TooManyRelayers
+        );
         <Relayers<T>>::insert(&relayer, true);
+        <RelayerList<T>>::append(&relayer);
         <RelayerCount>::mutate(|i| *i += 1);
+        let now = <frame_system::Module<T>>::block_number();
+        <RelayerActiveFrom<T>>::insert(&relayer, now + T::RelayerActivationDelay::get());
+        debug::debug!(target: LOG_TARGET, "Relayer {:?} added", relayer);
 
         Self::deposit_event(RawEvent::RelayerAdded(relayer));
+        Self::deposit_event(RawEvent::RelayerSetChanged(Self::relayer_set_hash()));
+        Ok(())
+    }
+
+    /// Adds an account to the admin members
+    pub fn register_admin(who: T::AccountId) -> DispatchResult {
+        ensure!(!Self::is_admin(&who), Error::<T>::AdminAlreadyExists);
+        <Admins<T>>::insert(&who, ());
+        Self::deposit_event(RawEvent::AdminAdded(who));
+        Ok(())
+    }
+
+    /// Removes an account from the admin members
+    pub fn unregister_admin(who: T::AccountId) -> DispatchResult {
+        ensure!(Self::is_admin(&who), Error::<T>::AdminInvalid);
+        <Admins<T>>::remove(&who);
+        Self::deposit_event(RawEvent::AdminRemoved(who));
         Ok(())
     }
 
@@ -425,82 +2077,608 @@ impl<T: Config> Module<T> {
     pub fn unregister_relayer(relayer: T::AccountId) -> DispatchResult {
         ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
         <Relayers<T>>::remove(&relayer);
+        <RelayerList<T>>::mutate(|list| list.retain(|r| r != &relayer));
+        <RelayerActiveFrom<T>>::remove(&relayer);
+        <LastHeartbeat<T>>::remove(&relayer);
+        <RelayerChains<T>>::remove_prefix(&relayer);
         <RelayerCount>::mutate(|i| *i -= 1);
+        debug::debug!(target: LOG_TARGET, "Relayer {:?} removed", relayer);
         Self::deposit_event(RawEvent::RelayerRemoved(relayer));
+        Self::deposit_event(RawEvent::RelayerSetChanged(Self::relayer_set_hash()));
+        Ok(())
+    }
+
+    /// Assigns a relayer to a chain, whitelisting the chain if `AutoWhitelistOnRelayerAssign`
+    /// is set and it isn't already
+    pub fn assign_chain(relayer: T::AccountId, id: ChainId) -> DispatchResult {
+        ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
+        if T::AutoWhitelistOnRelayerAssign::get() && !Self::chain_whitelisted(id) {
+            Self::whitelist(id)?;
+        }
+        <RelayerChains<T>>::insert(&relayer, id, true);
+        Self::deposit_event(RawEvent::RelayerChainAssigned(relayer, id));
+        Ok(())
+    }
+
+    /// Replaces the relayer set and threshold, and removes all proposals
+    pub fn replace_relayer_set(relayers: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+        ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+        Self::ensure_valid_relayer_set(&relayers)?;
+
+        <Relayers<T>>::remove_all();
+        <RelayerActiveFrom<T>>::remove_all();
+        <LastHeartbeat<T>>::remove_all();
+        <RelayerChains<T>>::remove_all();
+        <Votes<T>>::remove_all();
+        <ProposalKeys<T>>::remove_all();
+        <TotalActiveProposals>::kill();
+        for relayer in &relayers {
+            <Relayers<T>>::insert(relayer, true);
+        }
+        <RelayerList<T>>::put(&relayers);
+        <RelayerCount>::put(relayers.len() as u32);
+        <RelayerThreshold>::put(threshold);
+        debug::warn!(
+            target: LOG_TARGET,
+            "Relayer set replaced with {:?}, threshold {}",
+            relayers,
+            threshold
+        );
+
+        Self::deposit_event(RawEvent::RelayerSetReplaced(relayers));
+        Self::deposit_event(RawEvent::RelayerSetChanged(Self::relayer_set_hash()));
+        Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+        Ok(())
+    }
+
+    /// Current relayer set, whitelisted chains and resources of the bridge
+    #[cfg(feature = "std")]
+    pub fn export_state() -> BridgeStateSnapshot<T::AccountId> {
+        BridgeStateSnapshot {
+            relayers: Self::relayer_list(),
+            threshold: Self::relayer_threshold(),
+            chains: Self::chain_list()
+                .into_iter()
+                .map(|id| (id, Self::chains(id).unwrap_or_default()))
+                .collect(),
+            resources: Self::resource_list()
+                .into_iter()
+                .filter_map(|id| Self::resources(id).map(|(method, enabled)| (id, method, enabled)))
+                .collect(),
+        }
+    }
+
+    /// Replaces the relayer set, whitelisted chains and resources with those of `snapshot`
+    pub fn restore_state(snapshot: BridgeStateSnapshot<T::AccountId>) -> DispatchResult {
+        Self::ensure_batch_size(snapshot.chains.len())?;
+        Self::ensure_batch_size(snapshot.resources.len())?;
+        for (i, (id, _)) in snapshot.chains.iter().enumerate() {
+            ensure!(*id != T::ChainId::get(), Error::<T>::InvalidChainId);
+            ensure!(
+                !snapshot.chains[..i].iter().any(|(prev, _)| prev == id),
+                Error::<T>::ChainAlreadyWhitelisted
+            );
+        }
+        // Resources are checked here rather than when registered, as the current state has
+        // been cleared by then
+        ensure!(
+            snapshot.resources.iter().all(|(r_id, _, _)| {
+                T::ResourceIdScheme::matches_chain(*r_id, T::ChainId::get())
+                    || snapshot
+                        .chains
+                        .iter()
+                        .any(|(id, _)| T::ResourceIdScheme::matches_chain(*r_id, *id))
+            }),
+            Error::<T>::ResourceChainMismatch
+        );
+        ensure!(
+            snapshot.threshold as usize <= snapshot.relayers.len(),
+            Error::<T>::InvalidThreshold
+        );
+        Self::replace_relayer_set(snapshot.relayers, snapshot.threshold)?;
+
+        for id in Self::chain_list() {
+            Self::drop_chain(id, false);
+        }
+        for (id, nonce) in snapshot.chains {
+            Self::add_chain(id, nonce, false);
+        }
+        for id in <ResourceList>::take() {
+            <Resources>::remove(id);
+        }
+        <ResourceCount>::kill();
+        for (id, method, enabled) in snapshot.resources {
+            Self::register_resource(id, method)?;
+            if !enabled {
+                Self::toggle_resource(id, false)?;
+            }
+        }
+        Self::deposit_event(RawEvent::StateImported);
+        Ok(())
+    }
+
+    /// Replaces the relayer set and threshold together, keeping in-flight proposals. Relayers
+    /// that remain in the set keep their activation and heartbeat, new ones are subject to
+    /// `RelayerActivationDelay` as if added with `register_relayer`.
+    pub fn rotate_relayers(relayers: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
+        ensure!(
+            threshold > 0 && threshold as usize <= relayers.len(),
+            Error::<T>::InvalidThreshold
+        );
+        Self::ensure_valid_relayer_set(&relayers)?;
+
+        let retained: Vec<bool> = relayers.iter().map(Self::is_relayer).collect();
+        for relayer in Self::relayer_list() {
+            if !relayers.contains(&relayer) {
+                <RelayerActiveFrom<T>>::remove(&relayer);
+                <LastHeartbeat<T>>::remove(&relayer);
+                <RelayerChains<T>>::remove_prefix(&relayer);
+            }
+        }
+        // Relayers missing from `RelayerList` can't be found individually, so the whole set is
+        // cleared rather than only the listed relayers being removed
+        <Relayers<T>>::remove_all();
+        let active_from =
+            <frame_system::Module<T>>::block_number() + T::RelayerActivationDelay::get();
+        for (relayer, retained) in relayers.iter().zip(retained) {
+            <Relayers<T>>::insert(relayer, true);
+            if !retained {
+                <RelayerActiveFrom<T>>::insert(relayer, active_from);
+            }
+        }
+        <RelayerList<T>>::put(&relayers);
+        <RelayerCount>::put(relayers.len() as u32);
+        <RelayerThreshold>::put(threshold);
+        debug::debug!(
+            target: LOG_TARGET,
+            "Relayer set rotated to {:?}, threshold {}",
+            relayers,
+            threshold
+        );
+
+        Self::deposit_event(RawEvent::RelayerSetReplaced(relayers));
+        Self::deposit_event(RawEvent::RelayerSetChanged(Self::relayer_set_hash()));
+        Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+        Ok(())
+    }
+
+    /// Checks that a new relayer set is within `MaxBatchSize` and `MaxRelayers` and has no
+    /// duplicates
+    fn ensure_valid_relayer_set(relayers: &[T::AccountId]) -> DispatchResult {
+        Self::ensure_batch_size(relayers.len())?;
+        ensure!(
+            relayers.len() <= T::MaxRelayers::get() as usize,
+            Error::<T>::TooManyRelayers
+        );
+        for (i, relayer) in relayers.iter().enumerate() {
+            ensure!(
+                !relayers[..i].contains(relayer),
+                Error::<T>::RelayerAlreadyExists
+            );
+        }
         Ok(())
     }
 
+    /// Translates `Resources` and `Votes` from their layout up to version 0.0.2 of the pallet.
+    ///
+    /// Resources are enabled. The resource of an in-flight proposal isn't known, so it is left
+    /// empty until the next vote records it, and its creation block is derived from its expiry.
+    /// Proposals with more votes than `MaxRelayers` are dropped.
+    fn migrate_to_v0_0_3() -> Weight {
+        let mut translated: Weight = 0;
+        <Resources>::translate_values::<Vec<u8>, _>(|method| {
+            translated += 1;
+            Some((method, true))
+        });
+        <Votes<T>>::translate_values::<ProposalVotesV0<T::AccountId, T::BlockNumber>, _>(|old| {
+            translated += 1;
+            Some(ProposalVotes {
+                votes_for: BoundedVec::try_from(old.votes_for).ok()?,
+                votes_against: BoundedVec::try_from(old.votes_against).ok()?,
+                status: old.status,
+                expiry: old.expiry,
+                resource_id: [0; 32],
+                note: vec![],
+                created_at: old.expiry.saturating_sub(T::ProposalLifetime::get()),
+            })
+        });
+        debug::info!(target: LOG_TARGET, "Migrated {} resources and proposals", translated);
+        T::DbWeight::get().reads_writes(translated + 1, translated)
+    }
+
+    /// Removes up to `limit` entries of `Votes` for `src_id` whose value fails to decode,
+    /// returning the number removed.
+    ///
+    /// Entries are found by iterating the raw storage keys under the chain's prefix, as the
+    /// proposals in the keys are hashed and may not decode either. The matching `ProposalKeys`
+    /// entry is removed too, and since the status of an undecodable proposal is unknown it is
+    /// assumed to have been active.
+    pub fn remove_undecodable_votes(src_id: ChainId, limit: u32) -> u32 {
+        let mut prefix = <Votes<T>>::final_prefix().to_vec();
+        prefix.extend_from_slice(&src_id.using_encoded(sp_io::hashing::blake2_256));
+
+        let mut removed = 0;
+        let mut previous = prefix.clone();
+        while removed < limit {
+            let key = match sp_io::storage::next_key(&previous) {
+                Some(key) if key.starts_with(&prefix) => key,
+                _ => break,
+            };
+            let decodes = match unhashed::get_raw(&key) {
+                Some(raw) => ProposalVotes::<T::AccountId, T::BlockNumber, T::MaxRelayers>::decode(
+                    &mut &raw[..],
+                )
+                .is_ok(),
+                None => false,
+            };
+            if !decodes {
+                unhashed::kill(&key);
+                Self::remove_proposal_key_by_hash(src_id, &key[prefix.len()..]);
+                Self::proposal_deactivated();
+                removed += 1;
+            }
+            previous = key;
+        }
+        removed
+    }
+
+    /// Removes the `ProposalKeys` entry for `src_id` whose `(nonce, proposal)` encoding hashes
+    /// to `hash`, which is how the same entry is keyed in `Votes`
+    fn remove_proposal_key_by_hash(src_id: ChainId, hash: &[u8]) {
+        let mut prefix = <ProposalKeys<T>>::final_prefix().to_vec();
+        prefix.extend_from_slice(&src_id.using_encoded(sp_io::hashing::blake2_128));
+        prefix.extend_from_slice(&src_id.encode());
+
+        let mut previous = prefix.clone();
+        while let Some(key) = sp_io::storage::next_key(&previous) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            // The key ends with the blake2_128 hash of the encoding, followed by the encoding
+            let encoded = &key[(prefix.len() + 16).min(key.len())..];
+            if sp_io::hashing::blake2_256(encoded)[..] == *hash {
+                unhashed::kill(&key);
+                return;
+            }
+            previous = key;
+        }
+    }
+
     // *** Proposal voting and execution methods ***
 
+    /// Returns an empty, active proposal from `src_id` for `resource_id` created at block `now`
+    fn new_proposal_votes(
+        src_id: ChainId,
+        now: T::BlockNumber,
+        resource_id: ResourceId,
+        note: Vec<u8>,
+    ) -> ProposalVotes<T::AccountId, T::BlockNumber, T::MaxRelayers> {
+        let mut votes = ProposalVotes::default();
+        votes.expiry = now + Self::effective_lifetime(src_id);
+        votes.created_at = now;
+        votes.resource_id = resource_id;
+        votes.note = note;
+        votes
+    }
+
+    /// Sets the status of a proposal without voting or executing it, creating the proposal if
+    /// it doesn't exist. Only available in tests and benchmarks.
+    #[cfg(any(test, feature = "runtime-benchmarks"))]
+    pub fn set_proposal_status_for_testing(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        call: T::Proposal,
+        status: ProposalStatus,
+    ) {
+        let now = <frame_system::Module<T>>::block_number();
+        <ProposalKeys<T>>::insert(src_id, (nonce, call.clone()), ());
+        <Votes<T>>::mutate(src_id, (nonce, call), |maybe_votes| {
+            let was_active = matches!(maybe_votes, Some(votes) if !votes.is_complete());
+            let votes = maybe_votes.get_or_insert_with(|| {
+                Self::new_proposal_votes(src_id, now, ResourceId::default(), vec![])
+            });
+            votes.status = status;
+            match (was_active, votes.is_complete()) {
+                (false, false) => <TotalActiveProposals>::mutate(|n| *n = n.saturating_add(1)),
+                (true, true) => Self::proposal_deactivated(),
+                _ => (),
+            }
+        });
+    }
+
+    /// Counts a newly created proposal towards `MaxTotalActiveProposals`
+    fn proposal_activated() -> DispatchResult {
+        <TotalActiveProposals>::try_mutate(|active| {
+            ensure!(
+                *active < T::MaxTotalActiveProposals::get(),
+                Error::<T>::TooManyActiveProposals
+            );
+            *active += 1;
+            Ok(())
+        })
+    }
+
+    /// Stops counting a proposal that has been approved, rejected or removed
+    fn proposal_deactivated() {
+        <TotalActiveProposals>::mutate(|active| *active = active.saturating_sub(1));
+    }
+
+    /// Creates a proposal without recording a vote for it.
+    fn create(
+        nonce: DepositNonce,
+        src_id: ChainId,
+        r_id: ResourceId,
+        prop: Box<T::Proposal>,
+        note: Vec<u8>,
+    ) -> DispatchResult {
+        ensure!(!Self::is_paused(), Error::<T>::BridgeIsPaused);
+        ensure!(!Self::is_chain_paused(src_id), Error::<T>::ChainPaused);
+        ensure!(!Self::is_resource_paused(r_id), Error::<T>::ResourcePaused);
+        ensure!(
+            Self::resource_for_chain(r_id, src_id),
+            Error::<T>::ResourceChainMismatch
+        );
+        ensure!(
+            !<Votes<T>>::contains_key(src_id, (nonce, prop.clone())),
+            Error::<T>::ProposalAlreadyExists
+        );
+        ensure!(
+            !<CompletedProposals>::contains_key(src_id, nonce),
+            Error::<T>::ProposalAlreadyComplete
+        );
+        Self::proposal_activated()?;
+        let now = <frame_system::Module<T>>::block_number();
+        let votes = Self::new_proposal_votes(src_id, now, r_id, note.clone());
+        let hash = Self::proposal_hash(&prop);
+        <ProposalKeys<T>>::insert(src_id, (nonce, (*prop).clone()), ());
+        <Votes<T>>::insert(src_id, (nonce, prop), votes);
+        <TotalProposals>::mutate(|total| *total = total.saturating_add(1));
+        debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} created", nonce, src_id);
+        Self::deposit_event(RawEvent::ProposalCreated(src_id, nonce, hash, note));
+        Ok(())
+    }
+
     /// Commits a vote for a proposal. If the proposal doesn't exist it will be created.
     fn commit_vote(
         who: T::AccountId,
         nonce: DepositNonce,
         src_id: ChainId,
+        r_id: ResourceId,
         prop: Box<T::Proposal>,
         in_favour: bool,
     ) -> DispatchResult {
+        ensure!(!Self::is_paused(), Error::<T>::BridgeIsPaused);
+        ensure!(!Self::is_chain_paused(src_id), Error::<T>::ChainPaused);
+        ensure!(!Self::is_resource_paused(r_id), Error::<T>::ResourcePaused);
+        ensure!(
+            Self::resource_for_chain(r_id, src_id),
+            Error::<T>::ResourceChainMismatch
+        );
         let now = <frame_system::Module<T>>::block_number();
-        let mut votes = match <Votes<T>>::get(src_id, (nonce, prop.clone())) {
-            Some(v) => v,
-            None => {
-                let mut v = ProposalVotes::default();
-                v.expiry = now + T::ProposalLifetime::get();
-                v
-            }
-        };
-
-        // Ensure the proposal isn't complete and relayer hasn't already voted
-        ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
-        ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
-        ensure!(!votes.has_voted(&who), Error::<T>::RelayerAlreadyVoted);
-
-        if in_favour {
-            votes.votes_for.push(who.clone());
-            Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who.clone()));
-        } else {
-            votes.votes_against.push(who.clone());
-            Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who.clone()));
+        if let Some(active_from) = Self::relayer_active_from(&who) {
+            ensure!(now >= active_from, Error::<T>::RelayerNotYetActive);
         }
-
-        <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
-
-        Ok(())
+        ensure!(
+            Self::relayer_may_vote_from(&who, src_id),
+            Error::<T>::RelayerNotAssignedToChain
+        );
+        // Creating and voting on the proposal happen in a single mutation, so the first vote
+        // for a nonce creates it and any later vote is added to that same entry
+        <Votes<T>>::try_mutate(
+            src_id,
+            (nonce, (*prop).clone()),
+            |maybe_votes| -> DispatchResult {
+                let created = maybe_votes.is_none();
+                let votes = maybe_votes
+                    .get_or_insert_with(|| Self::new_proposal_votes(src_id, now, r_id, vec![]));
+
+                // Ensure the proposal isn't complete and relayer hasn't already voted
+                ensure!(
+                    votes.status != ProposalStatus::Approved,
+                    Error::<T>::ProposalAlreadyApproved
+                );
+                ensure!(
+                    votes.status != ProposalStatus::Rejected,
+                    Error::<T>::ProposalAlreadyRejected
+                );
+                // A nonce that has already been resolved can't be voted on again, even under a
+                // different proposal or after the original proposal has been removed
+                ensure!(
+                    !<CompletedProposals>::contains_key(src_id, nonce),
+                    Error::<T>::ProposalAlreadyComplete
+                );
+                // Proposals migrated from before resources were recorded take the resource of
+                // their next vote
+                if votes.resource_id == [0; 32] {
+                    votes.resource_id = r_id;
+                }
+                ensure!(votes.resource_id == r_id, Error::<T>::ResourceChainMismatch);
+                if votes.is_expired(now) {
+                    debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} expired", nonce, src_id);
+                    Err(Error::<T>::ProposalExpired)?
+                }
+                if votes.has_voted(&who) {
+                    if T::ReportDuplicateVotes::get() {
+                        Self::deposit_event(RawEvent::DuplicateVoteAttempt(
+                            src_id,
+                            nonce,
+                            who.clone(),
+                        ));
+                    }
+                    Err(Error::<T>::RelayerAlreadyVoted)?
+                }
+
+                // The vote is recorded before anything outside this entry is written, so a
+                // rejected vote leaves no trace of a new proposal
+                if in_favour {
+                    votes
+                        .votes_for
+                        .try_push(who.clone())
+                        .map_err(|_| Error::<T>::TooManyRelayers)?;
+                } else {
+                    votes
+                        .votes_against
+                        .try_push(who.clone())
+                        .map_err(|_| Error::<T>::TooManyRelayers)?;
+                }
+                if created {
+                    Self::proposal_activated()?;
+                    <ProposalKeys<T>>::insert(src_id, (nonce, (*prop).clone()), ());
+                    <TotalProposals>::mutate(|total| *total = total.saturating_add(1));
+                    debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} created", nonce, src_id);
+                    Self::deposit_event(RawEvent::ProposalCreated(
+                        src_id,
+                        nonce,
+                        Self::proposal_hash(&prop),
+                        vec![],
+                    ));
+                }
+                let counts = if T::VerboseVoteEvents::get() {
+                    Some((
+                        votes.votes_for.len() as u32,
+                        votes.votes_against.len() as u32,
+                    ))
+                } else {
+                    None
+                };
+                if in_favour {
+                    Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who.clone(), counts));
+                } else {
+                    Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who.clone(), counts));
+                }
+                Ok(())
+            },
+        )
     }
 
     /// Attempts to finalize or cancel the proposal if the vote count allows.
+    /// Returns the weight used by executing the proposal, if it was executed.
     fn try_resolve_proposal(
         nonce: DepositNonce,
         src_id: ChainId,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
+    ) -> Result<Option<Weight>, DispatchError> {
+        ensure!(!Self::is_paused(), Error::<T>::BridgeIsPaused);
+        ensure!(!Self::is_chain_paused(src_id), Error::<T>::ChainPaused);
         if let Some(mut votes) = <Votes<T>>::get(src_id, (nonce, prop.clone())) {
             let now = <frame_system::Module<T>>::block_number();
             ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
-            ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
+            ensure!(
+                !Self::is_resource_paused(votes.resource_id),
+                Error::<T>::ResourcePaused
+            );
+            if votes.is_expired(now) {
+                debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} expired", nonce, src_id);
+                Err(Error::<T>::ProposalExpired)?
+            }
 
-            let status = votes.try_to_complete(<RelayerThreshold>::get(), <RelayerCount>::get());
+            let mut status = match T::StakeThreshold::get() {
+                Some(fraction) => {
+                    let total = T::StakeProvider::total_stake();
+                    let sum = |voters: &[T::AccountId]| {
+                        voters.iter().fold(0u128, |acc, who| {
+                            acc.saturating_add(T::StakeProvider::stake_of(who))
+                        })
+                    };
+                    let weight_for = sum(&votes.votes_for);
+                    let weight_against = sum(&votes.votes_against);
+                    votes.try_to_complete_weighted(
+                        weight_for,
+                        weight_against,
+                        fraction.mul_ceil(total),
+                        total,
+                        <Quorum>::get(),
+                        T::TieBreak::get(),
+                    )
+                }
+                None => votes.try_to_complete(
+                    Self::proposal_threshold(votes.resource_id),
+                    <Quorum>::get(),
+                    <RelayerCount>::get(),
+                    T::TieBreak::get(),
+                ),
+            };
+            if status == ProposalStatus::Approved {
+                ensure!(
+                    now >= votes.created_at + T::MinConfirmations::get(),
+                    Error::<T>::AwaitingConfirmations
+                );
+                ensure!(
+                    !<SeenTransfers>::get(src_id, (nonce, votes.resource_id)),
+                    Error::<T>::NonceAlreadyUsed
+                );
+                ensure!(
+                    !T::RejectRemovedResources::get() || Self::resource_exists(votes.resource_id),
+                    Error::<T>::ResourceDoesNotExist
+                );
+                if !Self::resource_method_matches(votes.resource_id, &prop) {
+                    debug::warn!(
+                        target: LOG_TARGET,
+                        "Proposal {} from chain {} doesn't match its resource method",
+                        nonce,
+                        src_id
+                    );
+                    if T::MethodMismatch::get() == MethodMismatchMode::Reject {
+                        votes.status = ProposalStatus::Rejected;
+                        status = ProposalStatus::Rejected;
+                    }
+                }
+            }
+            if status == ProposalStatus::Approved {
+                ensure!(
+                    T::InboundRateLimiter::allow(src_id, votes.resource_id, now),
+                    Error::<T>::RateLimited
+                );
+            }
             <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+            if votes.is_complete() {
+                Self::record_outcome(src_id, nonce, status.clone());
+                Self::proposal_deactivated();
+            }
 
             match status {
-                ProposalStatus::Approved => Self::finalize_execution(src_id, nonce, prop),
-                ProposalStatus::Rejected => Self::cancel_execution(src_id, nonce),
-                _ => Ok(()),
+                ProposalStatus::Approved => {
+                    Self::finalize_execution(src_id, nonce, votes.resource_id, prop).map(Some)
+                }
+                ProposalStatus::Rejected => {
+                    Self::cancel_execution(src_id, nonce, votes.resource_id).map(|_| None)
+                }
+                _ => Ok(None),
             }
         } else {
             Err(Error::<T>::ProposalDoesNotExist)?
         }
     }
 
+    /// Resolves a proposal after a vote has been committed. If execution is awaiting
+    /// confirmations or rate limited the vote still stands, and the proposal can be resolved
+    /// later with `eval_vote_state`.
+    fn resolve_after_vote(
+        nonce: DepositNonce,
+        src_id: ChainId,
+        prop: Box<T::Proposal>,
+    ) -> Result<Option<Weight>, DispatchError> {
+        match Self::try_resolve_proposal(nonce, src_id, prop) {
+            Err(e)
+                if e == Error::<T>::AwaitingConfirmations.into()
+                    || e == Error::<T>::RateLimited.into() =>
+            {
+                Ok(None)
+            }
+            res => res,
+        }
+    }
+
     /// Commits a vote in favour of the proposal and executes it if the vote threshold is met.
     fn vote_for(
         who: T::AccountId,
         nonce: DepositNonce,
         src_id: ChainId,
+        r_id: ResourceId,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::commit_vote(who, nonce, src_id, prop.clone(), true)?;
-        Self::try_resolve_proposal(nonce, src_id, prop)
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::commit_vote(who, nonce, src_id, r_id, prop.clone(), true)?;
+        Self::resolve_after_vote(nonce, src_id, prop)
     }
 
     /// Commits a vote against the proposal and cancels it if more than (relayers.len() - threshold)
@@ -509,54 +2687,239 @@ impl<T: Config> Module<T> {
         who: T::AccountId,
         nonce: DepositNonce,
         src_id: ChainId,
+        r_id: ResourceId,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::commit_vote(who, nonce, src_id, prop.clone(), false)?;
-        Self::try_resolve_proposal(nonce, src_id, prop)
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::commit_vote(who, nonce, src_id, r_id, prop.clone(), false)?;
+        Self::resolve_after_vote(nonce, src_id, prop)
     }
 
-    /// Execute the proposal and signals the result as an event
+    /// Execute the proposal and signals the result as an event.
+    /// Returns the actual weight of the call plus the weight used by `OnProposalApproved`.
     fn finalize_execution(
         src_id: ChainId,
         nonce: DepositNonce,
+        resource_id: ResourceId,
         call: Box<T::Proposal>,
-    ) -> DispatchResult {
+    ) -> Result<Weight, DispatchError> {
+        debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} approved", nonce, src_id);
+        <SeenTransfers>::insert(src_id, (nonce, resource_id), true);
         Self::deposit_event(RawEvent::ProposalApproved(src_id, nonce));
-        call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into())
-            .map(|_| ())
-            .map_err(|e| e.error)?;
+        if !T::ProposalFilter::filter(&call) {
+            debug::warn!(
+                target: LOG_TARGET,
+                "Proposal {} from chain {} is no longer allowed and was not executed",
+                nonce,
+                src_id
+            );
+            Self::deposit_event(RawEvent::ProposalFilteredAtExecution(src_id, nonce));
+            Self::deposit_event(RawEvent::ProposalFailed(src_id, nonce));
+            T::OnProposalResolved::on_proposal_resolved(
+                src_id,
+                nonce,
+                resource_id,
+                ProposalStatus::Approved,
+                false,
+            );
+            return Ok(0);
+        }
+        let info = call.get_dispatch_info();
+        <BridgeCallDepth>::mutate(|depth| *depth = depth.saturating_add(1));
+        let result = call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into());
+        <BridgeCallDepth>::mutate(|depth| *depth = depth.saturating_sub(1));
+        let post_info = result.map_err(|e| {
+            debug::warn!(
+                target: LOG_TARGET,
+                "Proposal {} from chain {} failed to execute: {:?}",
+                nonce,
+                src_id,
+                e.error
+            );
+            T::OnProposalResolved::on_proposal_resolved(
+                src_id,
+                nonce,
+                resource_id,
+                ProposalStatus::Approved,
+                false,
+            );
+            e.error
+        })?;
+        <LastApproved>::mutate(src_id, |last| {
+            if *last < Some(nonce) {
+                *last = Some(nonce);
+            }
+        });
+        let call_weight = post_info.calc_actual_weight(&info);
         Self::deposit_event(RawEvent::ProposalSucceeded(src_id, nonce));
+        Self::deposit_event(RawEvent::ProposalExecuted(src_id, nonce, call_weight));
+        T::OnProposalResolved::on_proposal_resolved(
+            src_id,
+            nonce,
+            resource_id,
+            ProposalStatus::Approved,
+            true,
+        );
+        Ok(call_weight.saturating_add(T::OnProposalApproved::on_proposal_approved(src_id, nonce)))
+    }
+
+    /// Records the outcome of a nonce. Only the first outcome is kept, so a later rejection of
+    /// another proposal with the same nonce can't hide an approval.
+    fn record_outcome(src_id: ChainId, nonce: DepositNonce, status: ProposalStatus) {
+        <CompletedProposals>::mutate(src_id, nonce, |outcome| {
+            if outcome.is_none() {
+                *outcome = Some(status);
+            }
+        });
+    }
+
+    /// Marks an active proposal as rejected without considering its votes.
+    fn veto_proposal(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop: Box<T::Proposal>,
+    ) -> DispatchResult {
+        let mut votes = <Votes<T>>::get(src_id, (nonce, prop.clone()))
+            .ok_or(Error::<T>::ProposalDoesNotExist)?;
+        ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
+
+        votes.status = ProposalStatus::Rejected;
+        let resource_id = votes.resource_id;
+        <Votes<T>>::insert(src_id, (nonce, prop), votes);
+        Self::record_outcome(src_id, nonce, ProposalStatus::Rejected);
+        Self::proposal_deactivated();
+
+        Self::cancel_execution(src_id, nonce, resource_id)
+    }
+
+    /// Removes an undecided proposal whose lifetime has passed
+    fn remove_expired_proposal(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop: Box<T::Proposal>,
+    ) -> DispatchResult {
+        let votes = <Votes<T>>::get(src_id, (nonce, prop.clone()))
+            .ok_or(Error::<T>::ProposalDoesNotExist)?;
+        ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
+        let now = <frame_system::Module<T>>::block_number();
+        ensure!(votes.is_expired(now), Error::<T>::ProposalNotExpired);
+
+        for voter in votes.votes_for.iter() {
+            <VoteProofs<T>>::remove(src_id, (nonce, (*prop).clone(), voter));
+        }
+        <ProposalKeys<T>>::remove(src_id, (nonce, (*prop).clone()));
+        <Votes<T>>::remove(src_id, (nonce, *prop));
+        Self::record_outcome(src_id, nonce, ProposalStatus::Rejected);
+        Self::proposal_deactivated();
+        debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} expired", nonce, src_id);
+        Self::deposit_event(RawEvent::ProposalExpired(src_id, nonce));
+        T::OnProposalResolved::on_proposal_resolved(
+            src_id,
+            nonce,
+            votes.resource_id,
+            ProposalStatus::Rejected,
+            false,
+        );
         Ok(())
     }
 
     /// Cancels a proposal.
-    fn cancel_execution(src_id: ChainId, nonce: DepositNonce) -> DispatchResult {
+    fn cancel_execution(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        resource_id: ResourceId,
+    ) -> DispatchResult {
+        debug::debug!(target: LOG_TARGET, "Proposal {} from chain {} rejected", nonce, src_id);
         Self::deposit_event(RawEvent::ProposalRejected(src_id, nonce));
+        T::OnProposalResolved::on_proposal_resolved(
+            src_id,
+            nonce,
+            resource_id,
+            ProposalStatus::Rejected,
+            false,
+        );
         Ok(())
     }
 
     /// Initiates a transfer of a fungible asset out of the chain. This should be called by another pallet.
+    ///
+    /// `metadata` is passed through to the event as-is and may be left empty. `source` is the
+    /// local account that initiated the transfer, if any, and is recorded in the event. The same
+    /// applies to the other `transfer_*` functions.
     pub fn transfer_fungible(
         dest_id: ChainId,
         resource_id: ResourceId,
         to: Vec<u8>,
         amount: U256,
+        metadata: Vec<u8>,
+        source: Option<T::AccountId>,
     ) -> DispatchResult {
+        Self::ensure_can_transfer(dest_id, resource_id)?;
         ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
+            metadata.len() <= T::MaxFungibleMetadataLen::get() as usize,
+            Error::<T>::MetadataTooLong
         );
         let nonce = Self::bump_nonce(dest_id);
-        Self::deposit_event(RawEvent::FungibleTransfer(
+        Self::record_transfer(dest_id, nonce, resource_id);
+        Self::deposit_transfer_event(
             dest_id,
             nonce,
-            resource_id,
-            amount,
-            to,
-        ));
+            TransferRecord::Fungible(resource_id, amount, to, metadata, source),
+        );
+        Ok(())
+    }
+
+    /// Initiates a fungible transfer to each of `recipients`, emitting one `FungibleTransfer`
+    /// per recipient. This should be called by another pallet.
+    pub fn transfer_fungible_multi(
+        dest_id: ChainId,
+        resource_id: ResourceId,
+        recipients: Vec<(Vec<u8>, U256)>,
+        source: Option<T::AccountId>,
+    ) -> DispatchResult {
+        Self::ensure_can_transfer(dest_id, resource_id)?;
+        Self::ensure_batch_size(recipients.len())?;
+        ensure!(
+            !recipients.is_empty() && recipients.len() <= T::MaxFungibleRecipients::get() as usize,
+            Error::<T>::InvalidRecipientCount
+        );
+        for (to, amount) in recipients {
+            let nonce = Self::bump_nonce(dest_id);
+            Self::record_transfer(dest_id, nonce, resource_id);
+            Self::deposit_transfer_event(
+                dest_id,
+                nonce,
+                TransferRecord::Fungible(resource_id, amount, to, vec![], source.clone()),
+            );
+        }
         Ok(())
     }
 
+    /// Number of `FungibleTransfer` events, and so deposit nonces, a `transfer_fungible_multi`
+    /// to `recipients` recipients will produce. This is 0 for a number of recipients the
+    /// transfer would reject.
+    pub fn estimate_transfer_events(recipients: usize) -> u32 {
+        let max = T::MaxFungibleRecipients::get().min(T::MaxBatchSize::get()) as usize;
+        if (1..=max).contains(&recipients) {
+            recipients as u32
+        } else {
+            0
+        }
+    }
+
+    /// Weight of a `transfer_fungible_multi` to `recipients` recipients, for pallets building
+    /// their own calls on top of it
+    pub fn transfer_fungible_multi_weight(recipients: usize) -> Weight {
+        let transfers = Self::estimate_transfer_events(recipients) as Weight;
+        // Pause state, chain whitelist and resource status are checked once. Each transfer bumps
+        // the chain nonce and the per-resource, global and per-block transfer counters, and is
+        // added to the recent transfers.
+        T::DbWeight::get().reads(3).saturating_add(
+            T::DbWeight::get()
+                .reads_writes(5, 5)
+                .saturating_mul(transfers),
+        )
+    }
+
     /// Initiates a transfer of a nonfungible asset out of the chain. This should be called by another pallet.
     pub fn transfer_nonfungible(
         dest_id: ChainId,
@@ -564,20 +2927,24 @@ impl<T: Config> Module<T> {
         token_id: Vec<u8>,
         to: Vec<u8>,
         metadata: Vec<u8>,
+        source: Option<T::AccountId>,
     ) -> DispatchResult {
+        Self::ensure_can_transfer(dest_id, resource_id)?;
         ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
+            token_id.len() <= T::MaxTokenIdLen::get() as usize,
+            Error::<T>::TokenIdTooLong
+        );
+        ensure!(
+            metadata.len() <= T::MaxNftMetadataLen::get() as usize,
+            Error::<T>::MetadataTooLong
         );
         let nonce = Self::bump_nonce(dest_id);
-        Self::deposit_event(RawEvent::NonFungibleTransfer(
+        Self::record_transfer(dest_id, nonce, resource_id);
+        Self::deposit_transfer_event(
             dest_id,
             nonce,
-            resource_id,
-            token_id,
-            to,
-            metadata,
-        ));
+            TransferRecord::NonFungible(resource_id, token_id, to, metadata, source),
+        );
         Ok(())
     }
 
@@ -586,20 +2953,73 @@ impl<T: Config> Module<T> {
         dest_id: ChainId,
         resource_id: ResourceId,
         metadata: Vec<u8>,
+        source: Option<T::AccountId>,
     ) -> DispatchResult {
-        ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
+        Self::ensure_can_transfer(dest_id, resource_id)?;
+        let nonce = Self::bump_nonce(dest_id);
+        Self::record_transfer(dest_id, nonce, resource_id);
+        Self::deposit_transfer_event(
+            dest_id,
+            nonce,
+            TransferRecord::Generic(resource_id, metadata, source),
         );
+        Ok(())
+    }
+
+    /// Initiates a transfer of generic data out of the chain which expects a reply. The
+    /// caller-chosen `reply_nonce` is kept until the reply is matched with `handle_reply`.
+    pub fn transfer_generic_with_reply(
+        dest_id: ChainId,
+        resource_id: ResourceId,
+        metadata: Vec<u8>,
+        reply_nonce: DepositNonce,
+    ) -> DispatchResult {
+        Self::ensure_can_transfer(dest_id, resource_id)?;
         let nonce = Self::bump_nonce(dest_id);
-        Self::deposit_event(RawEvent::GenericTransfer(
+        Self::record_transfer(dest_id, nonce, resource_id);
+        <PendingReplies>::insert(dest_id, nonce, reply_nonce);
+        Self::deposit_transfer_event(
             dest_id,
             nonce,
-            resource_id,
-            metadata,
-        ));
+            TransferRecord::GenericWithReply(resource_id, metadata, reply_nonce),
+        );
         Ok(())
     }
+
+    /// Matches an inbound reply from `src_id` to the outbound transfer with `nonce`, returning
+    /// the reply nonce chosen by the original caller. This should be called by another pallet
+    /// handling the reply proposal.
+    pub fn handle_reply(
+        src_id: ChainId,
+        nonce: DepositNonce,
+    ) -> Result<DepositNonce, DispatchError> {
+        <PendingReplies>::take(src_id, nonce).ok_or_else(|| Error::<T>::ReplyNotPending.into())
+    }
+}
+
+/// Ensures the origin is signed by one of the `Admins` members.
+///
+/// This is already accepted by admin methods, and can be combined with other origins, e.g. with
+/// `EnsureOneOf`, elsewhere in the runtime.
+pub struct EnsureAdminMember<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> EnsureOrigin<T::Origin> for EnsureAdminMember<T> {
+    type Success = T::AccountId;
+    fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+        o.into().and_then(|o| match o {
+            system::RawOrigin::Signed(who) if <Module<T>>::is_admin(&who) => Ok(who),
+            r => Err(T::Origin::from(r)),
+        })
+    }
+
+    /// Returns an outer origin capable of passing `try_origin` check.
+    ///
+    /// ** Should be used for benchmarking only!!! **
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> T::Origin {
+        let who = T::AccountId::default();
+        <Admins<T>>::insert(&who, ());
+        T::Origin::from(frame_system::RawOrigin::Signed(who))
+    }
 }
 
 /// Simple ensure origin for the bridge account
@@ -619,9 +3039,6 @@ impl<T: Config> EnsureOrigin<T::Origin> for EnsureBridge<T> {
     /// ** Should be used for benchmarking only!!! **
     #[cfg(feature = "runtime-benchmarks")]
     fn successful_origin() -> T::Origin {
-        T::Origin::from(
-            frame_system::RawOrigin::Signed(<Module<T>>::account_id())
-        )
+        T::Origin::from(frame_system::RawOrigin::Signed(<Module<T>>::account_id()))
     }
-
 }
@@ -10,6 +10,7 @@ use sp_runtime::{
     traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
     Perbill,
 };
+use sp_std::collections::btree_map::BTreeMap;
 
 use crate::{self as bridge, Config};
 pub use pallet_balances as balances;
@@ -68,14 +69,209 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const TestChainId: u8 = 5;
     pub const ProposalLifetime: u64 = 50;
+    pub const MaxFungibleMetadataLen: u32 = 32;
+    pub const MaxFungibleRecipients: u32 = 3;
+    pub const MaxBatchSize: u32 = 4;
+    pub const MaxTokenIdLen: u32 = 32;
+    pub const MaxNftMetadataLen: u32 = 64;
+    pub const MaxProposalNoteLen: u32 = 32;
+    pub const MaxVoteProofLen: u32 = 64;
+    pub const MaxChainNameLen: u32 = 16;
+    pub const MaxRecentTransfers: u32 = 3;
+    pub const TransferVolumeAlertThreshold: u32 = 3;
+    pub const TieBreak: TieBreakMode = TieBreakMode::ApproveWins;
+    pub const MaxBridgeDepth: u8 = 1;
+}
+
+parameter_types! {
+    pub static NonceStep: u64 = 1;
+    pub static MinConfirmations: u64 = 0;
+    pub static ReportDuplicateVotes: bool = false;
+    pub static RequireVoteProof: bool = false;
+    pub static RejectStaleNonces: bool = false;
+    pub static RejectRemovedResources: bool = false;
+    pub static AllowRejectCreatesProposal: bool = true;
+    pub static MethodMismatch: MethodMismatchMode = MethodMismatchMode::Reject;
+    pub static WhitelistChangeCooldown: u64 = 0;
+    pub static VerboseOnboarding: bool = false;
+    pub static VerboseVoteEvents: bool = false;
+    pub static MaxTotalActiveProposals: u32 = u32::MAX;
+    pub static AutoWhitelistOnRelayerAssign: bool = false;
+    pub static FilterRemarks: bool = false;
+    pub static UsePrefixResourceIds: bool = false;
+    pub static MaxRelayers: u32 = 3;
+    pub static StakeThreshold: Option<Perbill> = None;
+    pub static RelayerStakes: BTreeMap<u64, u128> = BTreeMap::new();
+    pub static RelayerActivationDelay: u64 = 0;
+    pub static ApprovalHookWeight: Weight = 0;
+    pub static ResolvedProposals: Vec<(ChainId, DepositNonce, ResourceId, ProposalStatus, bool)> =
+        vec![];
+    pub static WhitelistedChains: Vec<ChainId> = vec![];
+    pub static RemovedChains: Vec<ChainId> = vec![];
+    pub static InboundLimit: u32 = u32::MAX;
+    // (window, proposals executed in window)
+    pub static InboundUsage: (u64, u32) = (0, 0);
+}
+
+pub const INBOUND_WINDOW: u64 = 10;
+
+/// Allows `InboundLimit` proposals to execute in each window of `INBOUND_WINDOW` blocks
+pub struct WindowRateLimiter;
+impl InboundRateLimiter<u64> for WindowRateLimiter {
+    fn allow(_src_id: ChainId, _resource_id: ResourceId, now: u64) -> bool {
+        let window = now / INBOUND_WINDOW;
+        let (current, used) = InboundUsage::get();
+        let used = if current == window { used } else { 0 };
+        if used >= InboundLimit::get() {
+            return false;
+        }
+        InboundUsage::set((window, used + 1));
+        true
+    }
+}
+
+/// Uses the stakes in `RelayerStakes`, relayers without an entry have no stake
+pub struct MockStake;
+impl RelayerStake<u64> for MockStake {
+    fn stake_of(who: &u64) -> u128 {
+        RelayerStakes::get().get(who).copied().unwrap_or_default()
+    }
+
+    fn total_stake() -> u128 {
+        RelayerStakes::get().values().sum()
+    }
+}
+
+/// Reports `ApprovalHookWeight` for every executed proposal
+pub struct MockApprovalHook;
+impl OnProposalApproved for MockApprovalHook {
+    fn on_proposal_approved(_src_id: ChainId, _nonce: DepositNonce) -> Weight {
+        ApprovalHookWeight::get()
+    }
+}
+
+/// Records every resolved proposal in `ResolvedProposals`
+pub struct MockResolutionHook;
+impl OnProposalResolved for MockResolutionHook {
+    fn on_proposal_resolved(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        resource_id: ResourceId,
+        status: ProposalStatus,
+        executed: bool,
+    ) {
+        let mut resolved = ResolvedProposals::get();
+        resolved.push((src_id, nonce, resource_id, status, executed));
+        ResolvedProposals::set(resolved);
+    }
+}
+
+/// Records the chains passed to the whitelist hooks in `WhitelistedChains` and `RemovedChains`
+pub struct MockChainHooks;
+impl OnChainWhitelisted for MockChainHooks {
+    fn on_chain_whitelisted(id: ChainId) {
+        let mut chains = WhitelistedChains::get();
+        chains.push(id);
+        WhitelistedChains::set(chains);
+    }
+}
+impl OnChainRemoved for MockChainHooks {
+    fn on_chain_removed(id: ChainId) {
+        let mut chains = RemovedChains::get();
+        chains.push(id);
+        RemovedChains::set(chains);
+    }
+}
+
+/// Rejects `System::remark` calls while `FilterRemarks` is set
+pub struct MockProposalFilter;
+impl Filter<Call> for MockProposalFilter {
+    fn filter(call: &Call) -> bool {
+        !(FilterRemarks::get() && matches!(call, Call::System(system::Call::remark(..))))
+    }
+}
+
+/// Places the chain ID in the first byte, followed by up to 31 bytes of the ID
+pub struct PrefixResourceId;
+impl ResourceIdScheme for PrefixResourceId {
+    fn derive(chain: ChainId, id: &[u8]) -> ResourceId {
+        let mut r_id: ResourceId = [0; 32];
+        r_id[0] = chain;
+        let len = id.len().min(31);
+        r_id[1..=len].copy_from_slice(&id[..len]);
+        r_id
+    }
+
+    fn matches_chain(r_id: ResourceId, chain: ChainId) -> bool {
+        r_id[0] == chain
+    }
+}
+
+/// Uses `PrefixResourceId` while `UsePrefixResourceIds` is set, `EvmResourceId` otherwise
+pub struct MockResourceIdScheme;
+impl ResourceIdScheme for MockResourceIdScheme {
+    fn derive(chain: ChainId, id: &[u8]) -> ResourceId {
+        if UsePrefixResourceIds::get() {
+            PrefixResourceId::derive(chain, id)
+        } else {
+            EvmResourceId::derive(chain, id)
+        }
+    }
+
+    fn matches_chain(r_id: ResourceId, chain: ChainId) -> bool {
+        if UsePrefixResourceIds::get() {
+            PrefixResourceId::matches_chain(r_id, chain)
+        } else {
+            EvmResourceId::matches_chain(r_id, chain)
+        }
+    }
 }
 
 impl Config for Test {
     type Event = Event;
     type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type VetoOrigin = frame_system::EnsureSignedBy<One, Self::AccountId>;
+    type TransferOrigin = frame_system::EnsureSignedBy<One, Self::AccountId>;
     type Proposal = Call;
     type ChainId = TestChainId;
     type ProposalLifetime = ProposalLifetime;
+    type MaxRelayers = MaxRelayers;
+    type MaxFungibleMetadataLen = MaxFungibleMetadataLen;
+    type MaxFungibleRecipients = MaxFungibleRecipients;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxTokenIdLen = MaxTokenIdLen;
+    type MaxNftMetadataLen = MaxNftMetadataLen;
+    type MaxProposalNoteLen = MaxProposalNoteLen;
+    type MaxVoteProofLen = MaxVoteProofLen;
+    type MaxChainNameLen = MaxChainNameLen;
+    type MaxRecentTransfers = MaxRecentTransfers;
+    type MaxTotalActiveProposals = MaxTotalActiveProposals;
+    type NonceStep = NonceStep;
+    type TransferVolumeAlertThreshold = TransferVolumeAlertThreshold;
+    type MinConfirmations = MinConfirmations;
+    type ReportDuplicateVotes = ReportDuplicateVotes;
+    type InboundRateLimiter = WindowRateLimiter;
+    type RequireVoteProof = RequireVoteProof;
+    type RejectStaleNonces = RejectStaleNonces;
+    type RejectRemovedResources = RejectRemovedResources;
+    type AllowRejectCreatesProposal = AllowRejectCreatesProposal;
+    type MethodMismatch = MethodMismatch;
+    type WhitelistChangeCooldown = WhitelistChangeCooldown;
+    type VerboseOnboarding = VerboseOnboarding;
+    type VerboseVoteEvents = VerboseVoteEvents;
+    type AutoWhitelistOnRelayerAssign = AutoWhitelistOnRelayerAssign;
+    type ProposalFilter = MockProposalFilter;
+    type TieBreak = TieBreak;
+    type StakeProvider = MockStake;
+    type StakeThreshold = StakeThreshold;
+    type RelayerActivationDelay = RelayerActivationDelay;
+    type OnProposalApproved = MockApprovalHook;
+    type OnProposalResolved = MockResolutionHook;
+    type MaxBridgeDepth = MaxBridgeDepth;
+    type OnChainWhitelisted = MockChainHooks;
+    type OnChainRemoved = MockChainHooks;
+    type WeightInfo = ();
+    type ResourceIdScheme = MockResourceIdScheme;
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
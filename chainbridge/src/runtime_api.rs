@@ -0,0 +1,21 @@
+//! Runtime API definition for the bridge pallet.
+
+// The generated client-side code takes more arguments than clippy allows, and repeats the
+// bounds on the API's type parameters.
+#![allow(clippy::too_many_arguments, clippy::multiple_bound_locations)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    /// Exposes bridge state that off-chain tooling needs without decoding storage.
+    pub trait ChainBridgeApi<AccountId, BlockNumber> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Whether proposals can currently be finalized. See `Module::can_finalize`.
+        fn can_finalize() -> bool;
+        /// Each relayer and the block of its last heartbeat. See `Module::relayers_status`.
+        fn relayers_status() -> Vec<(AccountId, Option<BlockNumber>)>;
+    }
+}
@@ -1,12 +1,36 @@
 #![cfg(test)]
 
 use super::mock::{
-    assert_events, new_test_ext, Balances, Bridge, Call, Event, Origin, ProposalLifetime, System,
-    Test, TestChainId, ENDOWED_BALANCE, RELAYER_A, RELAYER_B, RELAYER_C, TEST_THRESHOLD,
+    assert_events, new_test_ext, AllowRejectCreatesProposal, ApprovalHookWeight,
+    AutoWhitelistOnRelayerAssign, Balances, Bridge, Call, Event, FilterRemarks, InboundLimit,
+    MaxBatchSize, MaxChainNameLen, MaxFungibleMetadataLen, MaxFungibleRecipients,
+    MaxNftMetadataLen, MaxProposalNoteLen, MaxRecentTransfers, MaxRelayers, MaxTokenIdLen,
+    MaxTotalActiveProposals, MaxVoteProofLen, MethodMismatch, MinConfirmations, NonceStep, One,
+    Origin, PrefixResourceId, ProposalLifetime, RejectRemovedResources, RejectStaleNonces,
+    RelayerActivationDelay, RelayerStakes, RemovedChains, ReportDuplicateVotes, RequireVoteProof,
+    ResolvedProposals, StakeThreshold, System, Test, TestChainId, TransferVolumeAlertThreshold,
+    UsePrefixResourceIds, VerboseOnboarding, VerboseVoteEvents, WhitelistChangeCooldown,
+    WhitelistedChains, ENDOWED_BALANCE, INBOUND_WINDOW, RELAYER_A, RELAYER_B, RELAYER_C,
+    TEST_THRESHOLD,
 };
 use super::*;
 use crate::mock::new_test_ext_initialized;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_err, assert_noop, assert_ok,
+    dispatch::DispatchError,
+    traits::{OnFinalize, OnInitialize, OnRuntimeUpgrade},
+    weights::{DispatchClass, GetDispatchInfo},
+};
+use sp_runtime::traits::Hash as HashT;
+use sp_std::convert::TryInto;
+
+const RELAYER_D: u64 = 0x5;
+
+fn set_hash(relayers: &[u64]) -> <Test as frame_system::Config>::Hash {
+    let mut relayers = relayers.to_vec();
+    relayers.sort();
+    <Test as frame_system::Config>::Hashing::hash_of(&relayers)
+}
 
 #[test]
 fn derive_ids() {
@@ -23,53 +47,227 @@ fn derive_ids() {
     assert_eq!(r_id, expected);
 }
 
+#[test]
+fn resource_matches_chain() {
+    for chain in &[0, 1, 5, 255] {
+        let r_id = derive_resource_id(*chain, b"hash");
+        assert!(super::resource_matches_chain(r_id, *chain));
+        assert!(!super::resource_matches_chain(r_id, chain.wrapping_add(1)));
+    }
+    assert!(super::resource_matches_chain([0; 32], 0));
+    assert!(!super::resource_matches_chain([1; 32], 0));
+    assert!(super::resource_matches_chain([1; 32], 1));
+}
+
+#[test]
+fn configured_resource_id_scheme() {
+    let src_id = 1;
+    new_test_ext().execute_with(|| {
+        UsePrefixResourceIds::set(true);
+        let r_id = PrefixResourceId::derive(src_id, b"remark");
+        let evm_r_id = derive_resource_id(src_id, b"remark");
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+
+        // Resources are validated against the onboarded chain with the configured scheme
+        assert_noop!(
+            Bridge::onboard_chain(
+                Origin::root(),
+                src_id,
+                vec![(evm_r_id, b"System.remark".to_vec())]
+            ),
+            Error::<Test>::ResourceChainMismatch
+        );
+        assert_ok!(Bridge::onboard_chain(
+            Origin::root(),
+            src_id,
+            vec![(r_id, b"System.remark".to_vec())]
+        ));
+        assert_noop!(
+            Bridge::set_resource(Origin::root(), evm_r_id, b"System.remark".to_vec()),
+            Error::<Test>::ResourceChainMismatch
+        );
+
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal)).unwrap().status,
+            ProposalStatus::Approved
+        );
+        UsePrefixResourceIds::set(false);
+    })
+}
+
 #[test]
 fn complete_proposal_approved() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![3],
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![3].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
     };
 
-    prop.try_to_complete(2, 3);
+    prop.try_to_complete(2, 0, 3, TieBreakMode::ApproveWins);
     assert_eq!(prop.status, ProposalStatus::Approved);
 }
 
 #[test]
 fn complete_proposal_rejected() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1],
-        votes_against: vec![2, 3],
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1].try_into().unwrap(),
+        votes_against: vec![2, 3].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
     };
 
-    prop.try_to_complete(2, 3);
+    prop.try_to_complete(2, 0, 3, TieBreakMode::ApproveWins);
     assert_eq!(prop.status, ProposalStatus::Rejected);
 }
 
+#[test]
+fn complete_proposal_tie() {
+    // A relayer was removed after voting, so both outcomes have enough votes
+    let tied: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![3, 4].try_into().unwrap(),
+        status: ProposalStatus::Initiated,
+        expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
+    };
+
+    let mut prop = tied.clone();
+    prop.try_to_complete(2, 0, 3, TieBreakMode::ApproveWins);
+    assert_eq!(prop.status, ProposalStatus::Approved);
+
+    let mut prop = tied.clone();
+    prop.try_to_complete(2, 0, 3, TieBreakMode::RejectWins);
+    assert_eq!(prop.status, ProposalStatus::Rejected);
+
+    // Without a tie the mode has no effect
+    let mut prop = tied;
+    prop.votes_against = vec![3].try_into().unwrap();
+    prop.try_to_complete(2, 0, 3, TieBreakMode::RejectWins);
+    assert_eq!(prop.status, ProposalStatus::Approved);
+}
+
+#[test]
+fn complete_proposal_weighted() {
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1].try_into().unwrap(),
+        votes_against: vec![2, 3].try_into().unwrap(),
+        status: ProposalStatus::Initiated,
+        expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
+    };
+
+    // A single vote carrying most of the stake outweighs two smaller ones
+    prop.try_to_complete_weighted(70, 30, 60, 100, 0, TieBreakMode::ApproveWins);
+    assert_eq!(prop.status, ProposalStatus::Approved);
+
+    prop.status = ProposalStatus::Initiated;
+    prop.try_to_complete_weighted(30, 70, 60, 100, 0, TieBreakMode::ApproveWins);
+    assert_eq!(prop.status, ProposalStatus::Rejected);
+}
+
+#[test]
+fn complete_proposal_no_relayers() {
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![3].try_into().unwrap(),
+        status: ProposalStatus::Initiated,
+        expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
+    };
+
+    prop.try_to_complete(2, 0, 0, TieBreakMode::ApproveWins);
+    assert_eq!(prop.status, ProposalStatus::Initiated);
+}
+
 #[test]
 fn complete_proposal_bad_threshold() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![],
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![].try_into().unwrap(),
+        status: ProposalStatus::Initiated,
+        expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
+    };
+
+    prop.try_to_complete(3, 0, 2, TieBreakMode::ApproveWins);
+    assert_eq!(prop.status, ProposalStatus::Initiated);
+
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![].try_into().unwrap(),
+        votes_against: vec![1, 2].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
     };
 
-    prop.try_to_complete(3, 2);
+    prop.try_to_complete(3, 0, 2, TieBreakMode::ApproveWins);
     assert_eq!(prop.status, ProposalStatus::Initiated);
+}
 
-    let mut prop = ProposalVotes {
-        votes_for: vec![],
-        votes_against: vec![1, 2],
+#[test]
+fn complete_proposal_below_quorum() {
+    let mut prop: ProposalVotes<u64, u64, MaxRelayers> = ProposalVotes {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        resource_id: [0; 32],
+        note: vec![],
+        created_at: 0,
     };
 
-    prop.try_to_complete(3, 2);
+    // Threshold is met, but not enough relayers have voted
+    prop.try_to_complete(2, 3, 3, TieBreakMode::ApproveWins);
     assert_eq!(prop.status, ProposalStatus::Initiated);
+
+    prop.votes_against = vec![3].try_into().unwrap();
+    prop.try_to_complete(2, 3, 3, TieBreakMode::ApproveWins);
+    assert_eq!(prop.status, ProposalStatus::Approved);
+}
+
+#[test]
+fn bounded_vec_respects_bound() {
+    let mut v: BoundedVec<u64, MaxRelayers> = vec![1, 2].try_into().unwrap();
+    assert_ok!(v.try_push(3));
+    assert_eq!(v.try_push(4), Err(()));
+    assert_eq!(v.into_inner(), vec![1, 2, 3]);
+
+    let too_long: Result<BoundedVec<u64, MaxRelayers>, ()> = vec![1, 2, 3, 4].try_into();
+    assert!(too_long.is_err());
+
+    // Decoding must not accept more elements than the bound allows
+    let encoded = vec![1u64, 2, 3, 4].encode();
+    assert!(BoundedVec::<u64, MaxRelayers>::decode(&mut &encoded[..]).is_err());
+    let encoded = vec![1u64, 2, 3].encode();
+    let decoded = BoundedVec::<u64, MaxRelayers>::decode(&mut &encoded[..]).unwrap();
+    assert_eq!(decoded.into_inner(), vec![1, 2, 3]);
 }
 
 #[test]
@@ -79,17 +277,73 @@ fn setup_resources() {
         let method = "Pallet.do_something".as_bytes().to_vec();
         let method2 = "Pallet.do_somethingElse".as_bytes().to_vec();
 
+        // Resources have to belong to this chain or a whitelisted one
+        assert_noop!(
+            Bridge::set_resource(Origin::root(), id, method.clone()),
+            Error::<Test>::ResourceChainMismatch
+        );
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 1));
         assert_ok!(Bridge::set_resource(Origin::root(), id, method.clone()));
-        assert_eq!(Bridge::resources(id), Some(method));
+        assert_eq!(Bridge::resources(id), Some((method, true)));
 
         assert_ok!(Bridge::set_resource(Origin::root(), id, method2.clone()));
-        assert_eq!(Bridge::resources(id), Some(method2));
+        assert_eq!(Bridge::resources(id), Some((method2, true)));
 
         assert_ok!(Bridge::remove_resource(Origin::root(), id));
         assert_eq!(Bridge::resources(id), None);
     })
 }
 
+#[test]
+fn toggle_resource() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let method = b"System.remark".to_vec();
+
+    new_test_ext_initialized(src_id, r_id, method.clone()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+
+        assert_ok!(Bridge::set_resource_enabled(Origin::root(), r_id, false));
+        assert_eq!(Bridge::resources(r_id), Some((method.clone(), false)));
+        assert!(!Bridge::resource_exists(r_id));
+
+        // Disabled resources can't be transferred or voted on
+        assert_noop!(
+            Bridge::transfer_generic(src_id, r_id, vec![], None),
+            Error::<Test>::ResourceDisabled
+        );
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ResourceDoesNotExist
+        );
+
+        // Re-enabling restores the resource with its original method
+        assert_ok!(Bridge::set_resource_enabled(Origin::root(), r_id, true));
+        assert_eq!(Bridge::resources(r_id), Some((method, true)));
+        assert_ok!(Bridge::transfer_generic(src_id, r_id, vec![], None));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal),
+            vec![]
+        ));
+
+        assert_noop!(
+            Bridge::set_resource_enabled(Origin::root(), [9; 32], false),
+            Error::<Test>::ResourceDoesNotExist
+        );
+    })
+}
+
 #[test]
 fn whitelist_chain() {
     new_test_ext().execute_with(|| {
@@ -101,7 +355,293 @@ fn whitelist_chain() {
             Error::<Test>::InvalidChainId
         );
 
-        assert_events(vec![Event::bridge(RawEvent::ChainWhitelisted(0))]);
+        assert_events(vec![Event::bridge(RawEvent::ChainWhitelisted(0, None))]);
+    })
+}
+
+#[test]
+fn remove_chain() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 1));
+        assert_ok!(Bridge::whitelist_chains_with_nonce(
+            Origin::root(),
+            vec![(2, 100)]
+        ));
+        assert_eq!(WhitelistedChains::get(), vec![1, 2]);
+        assert_ok!(Bridge::transfer_generic(1, [1; 32], vec![], None));
+
+        assert_noop!(
+            Bridge::remove_chain(Origin::signed(RELAYER_A), 1),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::remove_chain(Origin::root(), 1));
+        assert!(!Bridge::chain_whitelisted(1));
+        assert!(Bridge::chain_whitelisted(2));
+        assert_eq!(RemovedChains::get(), vec![1]);
+        assert_noop!(
+            Bridge::remove_chain(Origin::root(), 1),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_noop!(
+            Bridge::transfer_generic(1, [1; 32], vec![], None),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_events(vec![Event::bridge(RawEvent::ChainRemoved(1, 1))]);
+    })
+}
+
+#[test]
+fn whitelist_change_cooldown() {
+    new_test_ext().execute_with(|| {
+        WhitelistChangeCooldown::set(10);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 1));
+        assert_eq!(Bridge::last_whitelist_change(), Some(1));
+
+        assert_noop!(
+            Bridge::whitelist_chain(Origin::root(), 2),
+            Error::<Test>::WhitelistChangeTooSoon
+        );
+        assert_noop!(
+            Bridge::whitelist_chains_with_nonce(Origin::root(), vec![(2, 100)]),
+            Error::<Test>::WhitelistChangeTooSoon
+        );
+        System::set_block_number(10);
+        assert_noop!(
+            Bridge::remove_chain(Origin::root(), 1),
+            Error::<Test>::WhitelistChangeTooSoon
+        );
+
+        System::set_block_number(11);
+        assert_ok!(Bridge::remove_chain(Origin::root(), 1));
+        assert_eq!(Bridge::last_whitelist_change(), Some(11));
+        assert_noop!(
+            Bridge::whitelist_chain(Origin::root(), 2),
+            Error::<Test>::WhitelistChangeTooSoon
+        );
+
+        System::set_block_number(21);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 2));
+    })
+}
+
+#[test]
+fn export_and_import_state() {
+    let snapshot = new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::set_threshold(Origin::root(), 2));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 1));
+        assert_ok!(Bridge::whitelist_chains_with_nonce(
+            Origin::root(),
+            vec![(2, 100), (3, 5)]
+        ));
+        assert_ok!(Bridge::transfer_generic(1, [1; 32], vec![], None));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            [1; 32],
+            b"A.a".to_vec()
+        ));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            [2; 32],
+            b"B.b".to_vec()
+        ));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            [3; 32],
+            b"C.c".to_vec()
+        ));
+        assert_ok!(Bridge::remove_resource(Origin::root(), [3; 32]));
+        assert_ok!(Bridge::remove_chain(Origin::root(), 3));
+        assert_ok!(Bridge::set_resource_enabled(Origin::root(), [2; 32], false));
+
+        let snapshot = Bridge::export_state();
+        assert_eq!(
+            snapshot,
+            BridgeStateSnapshot {
+                relayers: vec![RELAYER_A, RELAYER_B],
+                threshold: 2,
+                chains: vec![(1, 1), (2, 100)],
+                resources: vec![
+                    ([1; 32], b"A.a".to_vec(), true),
+                    ([2; 32], b"B.b".to_vec(), false)
+                ],
+            }
+        );
+        snapshot
+    });
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 4));
+        assert_noop!(
+            Bridge::import_state(Origin::signed(RELAYER_A), snapshot.clone()),
+            DispatchError::BadOrigin
+        );
+        System::set_block_number(5);
+        // Every part of the snapshot is bounded by `MaxBatchSize`
+        let too_many = (0..=MaxBatchSize::get() as u8).map(|i| 10 + i);
+        let mut oversized = snapshot.clone();
+        oversized.relayers = too_many.clone().map(u64::from).collect();
+        assert_noop!(
+            Bridge::import_state(Origin::root(), oversized),
+            Error::<Test>::BatchTooLarge
+        );
+        let mut oversized = snapshot.clone();
+        oversized.chains = too_many.clone().map(|id| (id, 0)).collect();
+        assert_noop!(
+            Bridge::import_state(Origin::root(), oversized),
+            Error::<Test>::BatchTooLarge
+        );
+        let mut oversized = snapshot.clone();
+        oversized.resources = too_many.map(|i| ([i; 32], vec![], true)).collect();
+        assert_noop!(
+            Bridge::import_state(Origin::root(), oversized),
+            Error::<Test>::BatchTooLarge
+        );
+        // The threshold has to be reachable, and resources have to belong to an imported chain
+        let mut invalid = snapshot.clone();
+        invalid.threshold = invalid.relayers.len() as u32 + 1;
+        assert_noop!(
+            Bridge::import_state(Origin::root(), invalid),
+            Error::<Test>::InvalidThreshold
+        );
+        let mut invalid = snapshot.clone();
+        invalid.resources.push(([4; 32], b"D.d".to_vec(), true));
+        assert_noop!(
+            Bridge::import_state(Origin::root(), invalid),
+            Error::<Test>::ResourceChainMismatch
+        );
+
+        // Larger snapshots weigh more
+        let mut larger = snapshot.clone();
+        larger.chains.push((3, 0));
+        assert!(
+            Call::Bridge(crate::Call::import_state(larger))
+                .get_dispatch_info()
+                .weight
+                > Call::Bridge(crate::Call::import_state(snapshot.clone()))
+                    .get_dispatch_info()
+                    .weight
+        );
+
+        WhitelistedChains::set(vec![]);
+        RemovedChains::set(vec![]);
+        assert_ok!(Bridge::import_state(Origin::root(), snapshot.clone()));
+
+        // Chain changes go through the whitelist hooks
+        assert_eq!(WhitelistedChains::get(), vec![1, 2]);
+        assert_eq!(RemovedChains::get(), vec![4]);
+        assert_eq!(Bridge::last_whitelist_change(), Some(5));
+
+        assert_eq!(Bridge::export_state(), snapshot);
+        assert!(!Bridge::chain_whitelisted(4));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_eq!(Bridge::resource_count(), 2);
+        assert!(Bridge::resource_exists([1; 32]));
+        assert!(!Bridge::resource_exists([2; 32]));
+        assert_events(vec![Event::bridge(RawEvent::StateImported)]);
+    })
+}
+
+#[test]
+fn onboard_chain() {
+    new_test_ext().execute_with(|| {
+        let resources = vec![
+            (derive_resource_id(1, b"a"), b"A.a".to_vec()),
+            (derive_resource_id(1, b"b"), b"B.b".to_vec()),
+        ];
+        assert_noop!(
+            Bridge::onboard_chain(Origin::signed(RELAYER_A), 1, resources.clone()),
+            DispatchError::BadOrigin
+        );
+
+        // Resources have to belong to the onboarded chain or this chain
+        assert_noop!(
+            Bridge::onboard_chain(
+                Origin::root(),
+                1,
+                vec![(derive_resource_id(2, b"a"), b"A.a".to_vec())]
+            ),
+            Error::<Test>::ResourceChainMismatch
+        );
+
+        assert_ok!(Bridge::onboard_chain(Origin::root(), 1, resources.clone()));
+        assert!(Bridge::chain_whitelisted(1));
+        assert!(Bridge::resource_exists(resources[0].0));
+        assert!(Bridge::resource_exists(resources[1].0));
+        assert_eq!(WhitelistedChains::get(), vec![1]);
+        assert_events(vec![Event::bridge(RawEvent::ChainOnboarded(1, 2))]);
+        assert!(!System::events()
+            .iter()
+            .any(|r| r.event == Event::bridge(RawEvent::ChainWhitelisted(1, None))));
+        assert_noop!(
+            Bridge::onboard_chain(Origin::root(), 1, vec![]),
+            Error::<Test>::ChainAlreadyWhitelisted
+        );
+
+        // Component events are emitted as well when verbose
+        VerboseOnboarding::set(true);
+        assert_ok!(Bridge::onboard_chain(
+            Origin::root(),
+            2,
+            vec![(derive_resource_id(2, b"c"), b"C.c".to_vec())]
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainWhitelisted(2, None)),
+            Event::bridge(RawEvent::ChainOnboarded(2, 1)),
+        ]);
+    })
+}
+
+#[test]
+fn whitelist_chains_with_nonce() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Bridge::whitelist_chains_with_nonce(Origin::signed(RELAYER_A), vec![(1, 0)]),
+            DispatchError::BadOrigin
+        );
+        // Nothing is whitelisted if any entry is invalid
+        assert_noop!(
+            Bridge::whitelist_chains_with_nonce(
+                Origin::root(),
+                vec![(1, 0), (TestChainId::get(), 100)]
+            ),
+            Error::<Test>::InvalidChainId
+        );
+        assert_noop!(
+            Bridge::whitelist_chains_with_nonce(Origin::root(), vec![(1, 0), (1, 100)]),
+            Error::<Test>::ChainAlreadyWhitelisted
+        );
+        let too_many = (1..=MaxBatchSize::get() as u8 + 1)
+            .map(|id| (id, 0))
+            .collect();
+        assert_noop!(
+            Bridge::whitelist_chains_with_nonce(Origin::root(), too_many),
+            Error::<Test>::BatchTooLarge
+        );
+
+        assert_ok!(Bridge::whitelist_chains_with_nonce(
+            Origin::root(),
+            vec![(1, 0), (2, 100)]
+        ));
+        assert_eq!(Bridge::chains(1), Some(0));
+        assert_eq!(Bridge::chains(2), Some(100));
+
+        // Transfers continue from the provided nonce
+        assert_ok!(Bridge::transfer_generic(2, [1; 32], vec![], None));
+        assert_eq!(Bridge::chains(2), Some(101));
+
+        assert_noop!(
+            Bridge::whitelist_chains_with_nonce(Origin::root(), vec![(2, 0)]),
+            Error::<Test>::ChainAlreadyWhitelisted
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainWhitelisted(1, None)),
+            Event::bridge(RawEvent::ChainWhitelisted(2, None)),
+            Event::bridge(RawEvent::GenericTransfer(2, 101, [1; 32], vec![], None)),
+        ]);
     })
 }
 
@@ -140,16 +680,20 @@ fn asset_transfer_success() {
             dest_id.clone(),
             resource_id.clone(),
             to.clone(),
-            amount.into()
+            amount.into(),
+            metadata.clone(),
+            None
         ));
         assert_events(vec![
-            Event::bridge(RawEvent::ChainWhitelisted(dest_id.clone())),
+            Event::bridge(RawEvent::ChainWhitelisted(dest_id.clone(), None)),
             Event::bridge(RawEvent::FungibleTransfer(
                 dest_id.clone(),
                 1,
                 resource_id.clone(),
                 amount.into(),
                 to.clone(),
+                metadata.clone(),
+                None,
             )),
         ]);
 
@@ -158,7 +702,8 @@ fn asset_transfer_success() {
             resource_id.clone(),
             token_id.clone(),
             to.clone(),
-            metadata.clone()
+            metadata.clone(),
+            None
         ));
         assert_events(vec![Event::bridge(RawEvent::NonFungibleTransfer(
             dest_id.clone(),
@@ -167,368 +712,4581 @@ fn asset_transfer_success() {
             token_id,
             to.clone(),
             metadata.clone(),
+            None,
         ))]);
 
         assert_ok!(Bridge::transfer_generic(
             dest_id.clone(),
             resource_id.clone(),
-            metadata.clone()
+            metadata.clone(),
+            None
         ));
         assert_events(vec![Event::bridge(RawEvent::GenericTransfer(
             dest_id.clone(),
             3,
             resource_id,
             metadata,
+            None,
         ))]);
     })
 }
 
 #[test]
-fn asset_transfer_invalid_chain() {
+fn fungible_transfer_metadata() {
     new_test_ext().execute_with(|| {
-        let chain_id = 2;
-        let bad_dest_id = 3;
-        let resource_id = [4; 32];
+        let dest_id = 2;
+        let to = vec![2];
+        let resource_id = [1; 32];
+        let amount = 100;
+        let metadata = b"memo:invoice-42".to_vec();
 
-        assert_ok!(Bridge::whitelist_chain(Origin::root(), chain_id.clone()));
-        assert_events(vec![Event::bridge(RawEvent::ChainWhitelisted(
-            chain_id.clone(),
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::transfer_fungible(
+            dest_id,
+            resource_id,
+            to.clone(),
+            amount.into(),
+            metadata.clone(),
+            None
+        ));
+        assert_events(vec![Event::bridge(RawEvent::FungibleTransfer(
+            dest_id,
+            1,
+            resource_id,
+            amount.into(),
+            to.clone(),
+            metadata,
+            None,
         ))]);
 
+        let too_long = vec![0u8; MaxFungibleMetadataLen::get() as usize + 1];
         assert_noop!(
-            Bridge::transfer_fungible(bad_dest_id, resource_id.clone(), vec![], U256::zero()),
-            Error::<Test>::ChainNotWhitelisted
+            Bridge::transfer_fungible(dest_id, resource_id, to, amount.into(), too_long, None),
+            Error::<Test>::MetadataTooLong
         );
+    })
+}
+
+#[test]
+fn chain_names() {
+    new_test_ext().execute_with(|| {
+        let named = 1;
+        let unnamed = 2;
+        let name = b"Ethereum".to_vec();
 
         assert_noop!(
-            Bridge::transfer_nonfungible(bad_dest_id, resource_id.clone(), vec![], vec![], vec![]),
-            Error::<Test>::ChainNotWhitelisted
+            Bridge::set_chain_name(Origin::signed(RELAYER_A), named, name.clone()),
+            DispatchError::BadOrigin
         );
-
         assert_noop!(
-            Bridge::transfer_generic(bad_dest_id, resource_id.clone(), vec![]),
-            Error::<Test>::ChainNotWhitelisted
+            Bridge::set_chain_name(
+                Origin::root(),
+                named,
+                vec![b'a'; MaxChainNameLen::get() as usize + 1]
+            ),
+            Error::<Test>::NameTooLong
         );
+
+        assert_ok!(Bridge::set_chain_name(Origin::root(), named, name.clone()));
+        assert_eq!(Bridge::chain_name(named), Some(name.clone()));
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), named));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), unnamed));
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainNameSet(named, name.clone())),
+            Event::bridge(RawEvent::ChainWhitelisted(named, Some(name))),
+            Event::bridge(RawEvent::ChainWhitelisted(unnamed, None)),
+        ]);
+
+        // An empty name clears it
+        assert_ok!(Bridge::set_chain_name(Origin::root(), named, vec![]));
+        assert_eq!(Bridge::chain_name(named), None);
     })
 }
 
 #[test]
-fn add_remove_relayer() {
+fn submit_transfers() {
     new_test_ext().execute_with(|| {
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
-        assert_eq!(Bridge::relayer_count(), 0);
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let to = vec![2];
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
 
+        assert_noop!(
+            Bridge::submit_fungible_transfer(
+                Origin::signed(RELAYER_A),
+                dest_id,
+                resource_id,
+                to.clone(),
+                100.into(),
+                vec![]
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::submit_nonfungible_transfer(
+                Origin::signed(RELAYER_A),
+                dest_id,
+                resource_id,
+                vec![1],
+                to.clone(),
+                vec![]
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::submit_generic_transfer(
+                Origin::signed(RELAYER_A),
+                dest_id,
+                resource_id,
+                vec![]
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Bridge::submit_fungible_transfer(
+            Origin::signed(One::get()),
+            dest_id,
+            resource_id,
+            to.clone(),
+            100.into(),
+            vec![]
+        ));
+        assert_ok!(Bridge::submit_nonfungible_transfer(
+            Origin::signed(One::get()),
+            dest_id,
+            resource_id,
+            vec![1],
+            to.clone(),
+            vec![]
+        ));
+        assert_ok!(Bridge::submit_generic_transfer(
+            Origin::signed(One::get()),
+            dest_id,
+            resource_id,
+            vec![3]
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::FungibleTransfer(
+                dest_id,
+                1,
+                resource_id,
+                100.into(),
+                to.clone(),
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::NonFungibleTransfer(
+                dest_id,
+                2,
+                resource_id,
+                vec![1],
+                to,
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::GenericTransfer(
+                dest_id,
+                3,
+                resource_id,
+                vec![3],
+                None,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn transfer_fungible_multi() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let recipients = vec![
+            (vec![1], U256::from(10)),
+            (vec![2], U256::from(20)),
+            (vec![3], U256::from(30)),
+        ];
+
+        assert_noop!(
+            Bridge::transfer_fungible_multi(dest_id, resource_id, recipients.clone(), None),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_noop!(
+            Bridge::transfer_fungible_multi(dest_id, resource_id, vec![], None),
+            Error::<Test>::InvalidRecipientCount
+        );
+        let mut too_many = recipients.clone();
+        too_many.push((vec![4], U256::from(40)));
+        assert_noop!(
+            Bridge::transfer_fungible_multi(dest_id, resource_id, too_many, None),
+            Error::<Test>::InvalidRecipientCount
+        );
+
+        assert_ok!(Bridge::transfer_fungible_multi(
+            dest_id,
+            resource_id,
+            recipients,
+            None
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::FungibleTransfer(
+                dest_id,
+                1,
+                resource_id,
+                10.into(),
+                vec![1],
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::FungibleTransfer(
+                dest_id,
+                2,
+                resource_id,
+                20.into(),
+                vec![2],
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::FungibleTransfer(
+                dest_id,
+                3,
+                resource_id,
+                30.into(),
+                vec![3],
+                vec![],
+                None,
+            )),
+        ]);
+        assert_eq!(Bridge::chains(dest_id), Some(3));
+    })
+}
+
+#[test]
+fn estimate_transfer_events_matches_batch() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let recipients = vec![
+            (vec![1], U256::from(10)),
+            (vec![2], U256::from(20)),
+            (vec![3], U256::from(30)),
+        ];
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        let events_before = System::events().len();
+        let nonce_before = Bridge::chains(dest_id).unwrap();
+        assert_ok!(Bridge::transfer_fungible_multi(
+            dest_id,
+            resource_id,
+            recipients.clone(),
+            None
+        ));
+
+        let expected = Bridge::estimate_transfer_events(recipients.len());
+        assert_eq!(expected, 3);
+        assert_eq!((System::events().len() - events_before) as u32, expected);
+        assert_eq!(
+            Bridge::chains(dest_id).unwrap() - nonce_before,
+            expected as u64
+        );
+    })
+}
+
+#[test]
+fn estimate_transfer_events_bounds() {
+    new_test_ext().execute_with(|| {
+        let max = MaxFungibleRecipients::get().min(MaxBatchSize::get());
+        assert_eq!(Bridge::estimate_transfer_events(0), 0);
+        assert_eq!(Bridge::estimate_transfer_events(1), 1);
+        assert_eq!(Bridge::estimate_transfer_events(max as usize), max);
+        // Batches the transfer would reject produce no events
+        assert_eq!(Bridge::estimate_transfer_events(max as usize + 1), 0);
+    })
+}
+
+#[test]
+fn transfer_nonfungible_bounds() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let token_id = vec![1; MaxTokenIdLen::get() as usize];
+        let metadata = vec![2; MaxNftMetadataLen::get() as usize];
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                dest_id,
+                resource_id,
+                vec![1; MaxTokenIdLen::get() as usize + 1],
+                vec![3],
+                metadata.clone(),
+                None
+            ),
+            Error::<Test>::TokenIdTooLong
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                dest_id,
+                resource_id,
+                token_id.clone(),
+                vec![3],
+                vec![2; MaxNftMetadataLen::get() as usize + 1],
+                None
+            ),
+            Error::<Test>::MetadataTooLong
+        );
+
+        // Both at their limits
+        assert_ok!(Bridge::transfer_nonfungible(
+            dest_id,
+            resource_id,
+            token_id.clone(),
+            vec![3],
+            metadata.clone(),
+            None
+        ));
+        assert_events(vec![Event::bridge(RawEvent::NonFungibleTransfer(
+            dest_id,
+            1,
+            resource_id,
+            token_id,
+            vec![3],
+            metadata,
+            None,
+        ))]);
+    })
+}
+
+#[test]
+fn transfer_events_record_source() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let source = 0x10;
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+
+        assert_ok!(Bridge::transfer_fungible(
+            dest_id,
+            resource_id,
+            vec![1],
+            100.into(),
+            vec![],
+            Some(source)
+        ));
+        assert_ok!(Bridge::transfer_nonfungible(
+            dest_id,
+            resource_id,
+            vec![1],
+            vec![1],
+            vec![],
+            Some(source)
+        ));
+        assert_ok!(Bridge::transfer_generic(
+            dest_id,
+            resource_id,
+            vec![],
+            Some(source)
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::FungibleTransfer(
+                dest_id,
+                1,
+                resource_id,
+                100.into(),
+                vec![1],
+                vec![],
+                Some(source),
+            )),
+            Event::bridge(RawEvent::NonFungibleTransfer(
+                dest_id,
+                2,
+                resource_id,
+                vec![1],
+                vec![1],
+                vec![],
+                Some(source),
+            )),
+            Event::bridge(RawEvent::GenericTransfer(
+                dest_id,
+                3,
+                resource_id,
+                vec![],
+                Some(source),
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn transfer_nonce_step() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        NonceStep::set(2);
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::transfer_fungible(
+            dest_id,
+            resource_id,
+            vec![1],
+            100.into(),
+            vec![],
+            None
+        ));
+        assert_ok!(Bridge::transfer_nonfungible(
+            dest_id,
+            resource_id,
+            vec![1],
+            vec![1],
+            vec![],
+            None
+        ));
+        assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::FungibleTransfer(
+                dest_id,
+                2,
+                resource_id,
+                100.into(),
+                vec![1],
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::NonFungibleTransfer(
+                dest_id,
+                4,
+                resource_id,
+                vec![1],
+                vec![1],
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::GenericTransfer(
+                dest_id,
+                6,
+                resource_id,
+                vec![],
+                None,
+            )),
+        ]);
+        assert_eq!(Bridge::chains(dest_id), Some(6));
+    })
+}
+
+#[test]
+fn resource_transfer_counters() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_a = [1; 32];
+        let resource_b = [2; 32];
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::transfer_fungible(
+            dest_id,
+            resource_a,
+            vec![1],
+            10.into(),
+            vec![],
+            None
+        ));
+        assert_ok!(Bridge::transfer_fungible(
+            dest_id,
+            resource_a,
+            vec![1],
+            20.into(),
+            vec![],
+            None
+        ));
+        assert_ok!(Bridge::transfer_generic(dest_id, resource_b, vec![], None));
+
+        assert_eq!(Bridge::resource_transfer_count(resource_a), 2);
+        assert_eq!(Bridge::resource_transfer_count(resource_b), 1);
+        // Counters are independent of the chain nonce
+        assert_eq!(Bridge::chains(dest_id), Some(3));
+    })
+}
+
+#[test]
+fn recent_transfers() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let limit = MaxRecentTransfers::get() as u64;
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        for i in 1..=limit + 2 {
+            assert_ok!(Bridge::transfer_generic(
+                dest_id,
+                [i as u8; 32],
+                vec![],
+                None
+            ));
+        }
+
+        // Only the latest transfers are kept, oldest first
+        let expected: Vec<_> = (3..=limit + 2)
+            .map(|i| (dest_id, i, [i as u8; 32]))
+            .collect();
+        assert_eq!(Bridge::recent_transfers().to_vec(), expected);
+    })
+}
+
+#[test]
+fn reemit_transfer() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let transfer = RawEvent::FungibleTransfer(
+            dest_id,
+            1,
+            resource_id,
+            10.into(),
+            vec![1],
+            vec![],
+            Some(RELAYER_A),
+        );
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::transfer_fungible(
+            dest_id,
+            resource_id,
+            vec![1],
+            10.into(),
+            vec![],
+            Some(RELAYER_A)
+        ));
+
+        assert_noop!(
+            Bridge::reemit_transfer(Origin::signed(RELAYER_A), dest_id, 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::reemit_transfer(Origin::root(), dest_id, 2),
+            Error::<Test>::TransferNotFound
+        );
+        assert_ok!(Bridge::reemit_transfer(Origin::root(), dest_id, 1));
+        assert_events(vec![
+            Event::bridge(transfer.clone()),
+            Event::bridge(transfer),
+            Event::bridge(RawEvent::TransferReplayed(dest_id, 1)),
+        ]);
+
+        // Transfers dropped from the recent transfers can't be replayed
+        for _ in 0..MaxRecentTransfers::get() {
+            assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+        }
+        assert_noop!(
+            Bridge::reemit_transfer(Origin::root(), dest_id, 1),
+            Error::<Test>::TransferNotFound
+        );
+        assert_ok!(Bridge::reemit_transfer(Origin::root(), dest_id, 2));
+    })
+}
+
+#[test]
+fn high_transfer_volume_alert() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+
+        // Staying at the threshold doesn't raise an alert
+        for _ in 0..TransferVolumeAlertThreshold::get() {
+            assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+        }
+        Bridge::on_finalize(1);
+        assert_events(vec![Event::bridge(RawEvent::GenericTransfer(
+            dest_id,
+            3,
+            resource_id,
+            vec![],
+            None,
+        ))]);
+
+        // Counter is reset each block, so only this block's transfers are counted
+        System::set_block_number(2);
+        for _ in 0..TransferVolumeAlertThreshold::get() + 1 {
+            assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+        }
+        Bridge::on_finalize(2);
+        assert_events(vec![
+            Event::bridge(RawEvent::GenericTransfer(
+                dest_id,
+                7,
+                resource_id,
+                vec![],
+                None,
+            )),
+            Event::bridge(RawEvent::HighTransferVolume(4)),
+        ]);
+    })
+}
+
+#[test]
+fn asset_transfer_invalid_chain() {
+    new_test_ext().execute_with(|| {
+        let chain_id = 2;
+        let bad_dest_id = 3;
+        let resource_id = [4; 32];
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), chain_id.clone()));
+        assert_events(vec![Event::bridge(RawEvent::ChainWhitelisted(
+            chain_id.clone(),
+            None,
+        ))]);
+
+        assert_noop!(
+            Bridge::transfer_fungible(
+                bad_dest_id,
+                resource_id.clone(),
+                vec![],
+                U256::zero(),
+                vec![],
+                None
+            ),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                bad_dest_id,
+                resource_id.clone(),
+                vec![],
+                vec![],
+                vec![],
+                None
+            ),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_noop!(
+            Bridge::transfer_generic(bad_dest_id, resource_id.clone(), vec![], None),
+            Error::<Test>::ChainNotWhitelisted
+        );
+    })
+}
+
+#[test]
+fn relayer_set_hash() {
+    new_test_ext().execute_with(|| {
+        let empty = Bridge::relayer_set_hash();
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        let only_b = Bridge::relayer_set_hash();
+        assert_ne!(only_b, empty);
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        let forward = Bridge::relayer_set_hash();
+        assert_ne!(forward, only_b);
+
+        // Adding the same relayers in a different order gives the same hash
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::relayer_list(), vec![RELAYER_A, RELAYER_B]);
+        assert_eq!(Bridge::relayer_set_hash(), forward);
+
+        assert_ok!(Bridge::replace_relayer_set(
+            vec![RELAYER_B, RELAYER_A],
+            TEST_THRESHOLD
+        ));
+        assert_eq!(Bridge::relayer_set_hash(), forward);
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerSetReplaced(vec![RELAYER_B, RELAYER_A])),
+            Event::bridge(RawEvent::RelayerSetChanged(forward)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(TEST_THRESHOLD)),
+        ]);
+    })
+}
+
+#[test]
+fn admin_members() {
+    new_test_ext().execute_with(|| {
+        let admin = 0x10;
+        let other = 0x11;
+
+        assert_noop!(
+            Bridge::add_admin(Origin::signed(admin), admin),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::set_threshold(Origin::signed(admin), 3),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Bridge::add_admin(Origin::root(), admin));
+        assert!(Bridge::is_admin(&admin));
+        assert_noop!(
+            Bridge::add_admin(Origin::root(), admin),
+            Error::<Test>::AdminAlreadyExists
+        );
+
+        // Members can call admin methods, but can't manage the members themselves
+        assert_ok!(Bridge::set_threshold(Origin::signed(admin), 3));
+        assert_eq!(Bridge::relayer_threshold(), 3);
+        assert_noop!(
+            Bridge::set_threshold(Origin::signed(other), 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::add_admin(Origin::signed(admin), other),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(
+            EnsureAdminMember::<Test>::try_origin(Origin::signed(admin)).ok(),
+            Some(admin)
+        );
+
+        assert_ok!(Bridge::remove_admin(Origin::root(), admin));
+        assert_noop!(
+            Bridge::remove_admin(Origin::root(), admin),
+            Error::<Test>::AdminInvalid
+        );
+        assert_noop!(
+            Bridge::set_threshold(Origin::signed(admin), 1),
+            DispatchError::BadOrigin
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::AdminAdded(admin)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(3)),
+            Event::bridge(RawEvent::AdminRemoved(admin)),
+        ]);
+    })
+}
+
+#[test]
+fn add_remove_relayer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
+        assert_eq!(Bridge::relayer_count(), 0);
+
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+        assert_eq!(Bridge::relayer_count(), 3);
+
+        // Already exists
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::RelayerAlreadyExists
+        );
+
+        // Confirm removal
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_noop!(
+            Bridge::remove_relayer(Origin::root(), RELAYER_B),
+            Error::<Test>::RelayerInvalid
+        );
+        assert_eq!(Bridge::relayer_count(), 2);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_A)),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[RELAYER_A]))),
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_B)),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_A, RELAYER_B,
+            ]))),
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_C)),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_A, RELAYER_B, RELAYER_C,
+            ]))),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_B)),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_A, RELAYER_C,
+            ]))),
+        ]);
+    })
+}
+
+#[test]
+fn add_relayer_beyond_max() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+        assert_eq!(Bridge::relayer_count(), MaxRelayers::get());
+
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_D),
+            Error::<Test>::TooManyRelayers
+        );
+    })
+}
+
+fn make_proposal(r: Vec<u8>) -> mock::Call {
+    Call::System(system::Call::remark(r))
+}
+
+#[test]
+fn create_sucessful_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Create proposal (& vote)
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Second relayer votes against
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![RELAYER_B].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Third relayer votes in favour
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A, RELAYER_C].try_into().unwrap(),
+            votes_against: vec![RELAYER_B].try_into().unwrap(),
+            status: ProposalStatus::Approved,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_C, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn verbose_vote_events() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        VerboseVoteEvents::set(true);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, Some((1, 0)))),
+            Event::bridge(RawEvent::VoteAgainst(
+                src_id,
+                prop_id,
+                RELAYER_B,
+                Some((1, 1)),
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_C, Some((2, 1)))),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn create_unsucessful_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![11]);
+
+        // Create proposal (& vote)
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Second relayer votes against
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![RELAYER_B].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Third relayer votes against
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![RELAYER_B, RELAYER_C].try_into().unwrap(),
+            status: ProposalStatus::Rejected,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        assert_eq!(Balances::free_balance(RELAYER_B), 0);
+        assert_eq!(
+            Balances::free_balance(Bridge::account_id()),
+            ENDOWED_BALANCE
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_C, None)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn execute_after_threshold_change() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![11]);
+
+        // Create proposal (& vote)
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Change threshold
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+
+        // Attempt to execute
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            Box::new(proposal.clone())
+        ));
+
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Approved,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        assert_eq!(Balances::free_balance(RELAYER_B), 0);
+        assert_eq!(
+            Balances::free_balance(Bridge::account_id()),
+            ENDOWED_BALANCE
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn transfer_executes_once_per_nonce_and_resource() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        // The same transfer, proposed with different calls
+        let first = make_proposal(vec![10]);
+        let second = make_proposal(vec![11]);
+        for proposal in &[first.clone(), second.clone()] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(first.clone()),
+            vec![]
+        ));
+        assert!(Bridge::transfer_seen(src_id, (prop_id, r_id)));
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(second.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, second.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Initiated
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                first.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn set_proposal_status_for_testing() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        Bridge::set_proposal_status_for_testing(
+            src_id,
+            prop_id,
+            proposal.clone(),
+            ProposalStatus::Approved,
+        );
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Approved);
+        assert!(prop.votes_for.is_empty());
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyApproved
+        );
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+    })
+}
+
+#[test]
+fn expire_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![1]
+        ));
+
+        assert_noop!(
+            Bridge::expire_proposal(
+                Origin::signed(ENDOWED_BALANCE),
+                SrcChainId(src_id),
+                prop_id + 1,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalDoesNotExist
+        );
+        // Still within the proposal's lifetime
+        System::set_block_number(ProposalLifetime::get());
+        assert_noop!(
+            Bridge::expire_proposal(
+                Origin::signed(ENDOWED_BALANCE),
+                SrcChainId(src_id),
+                prop_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalNotExpired
+        );
+
+        System::set_block_number(ProposalLifetime::get() + 1);
+        assert_ok!(Bridge::expire_proposal(
+            Origin::signed(ENDOWED_BALANCE),
+            SrcChainId(src_id),
+            prop_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(Bridge::votes(src_id, (prop_id, proposal.clone())), None);
+        assert_eq!(
+            Bridge::vote_proofs(src_id, (prop_id, proposal.clone(), RELAYER_A)),
+            None
+        );
+        assert_eq!(
+            Bridge::proposal_outcome(src_id, prop_id),
+            Some(ProposalStatus::Rejected)
+        );
+
+        // The nonce can't be proposed again
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        assert_events(vec![Event::bridge(RawEvent::ProposalExpired(
+            src_id, prop_id,
+        ))]);
+    })
+}
+
+#[test]
+fn effective_lifetime() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_eq!(Bridge::effective_lifetime(src_id), ProposalLifetime::get());
+        assert_eq!(Bridge::effective_lifetime(2), ProposalLifetime::get());
+
+        System::set_block_number(10);
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal.clone())).unwrap().expiry,
+            10 + Bridge::effective_lifetime(src_id)
+        );
+
+        // Overrides apply only to their chain
+        let lifetime = ProposalLifetime::get() * 2;
+        assert_noop!(
+            Bridge::set_chain_proposal_lifetime(Origin::signed(RELAYER_A), src_id, lifetime),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_chain_proposal_lifetime(
+            Origin::root(),
+            src_id,
+            lifetime
+        ));
+        assert_events(vec![Event::bridge(RawEvent::ChainProposalLifetimeChanged(
+            src_id, lifetime,
+        ))]);
+        assert_eq!(Bridge::effective_lifetime(src_id), lifetime);
+        assert_eq!(Bridge::effective_lifetime(2), ProposalLifetime::get());
+
+        // Existing proposals keep their expiry, new ones use the override
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal.clone())).unwrap().expiry,
+            10 + ProposalLifetime::get()
+        );
+        assert_eq!(
+            Bridge::votes(src_id, (2, proposal.clone())).unwrap().expiry,
+            10 + lifetime
+        );
+
+        // The stored lifetime applies to chains without an override
+        let stored = ProposalLifetime::get() * 3;
+        assert_noop!(
+            Bridge::set_proposal_lifetime(Origin::signed(RELAYER_A), stored),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_proposal_lifetime(Origin::root(), stored));
+        assert_events(vec![Event::bridge(RawEvent::ProposalLifetimeChanged(
+            stored,
+        ))]);
+        assert_eq!(Bridge::effective_lifetime(src_id), lifetime);
+        assert_eq!(Bridge::effective_lifetime(2), stored);
+
+        // A lifetime of zero clears the override
+        assert_ok!(Bridge::set_chain_proposal_lifetime(
+            Origin::root(),
+            src_id,
+            0
+        ));
+        assert_eq!(Bridge::chain_proposal_lifetime(src_id), None);
+        assert_eq!(Bridge::effective_lifetime(src_id), stored);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            3,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (3, proposal.clone())).unwrap().expiry,
+            10 + stored
+        );
+
+        // Clearing the stored lifetime falls back to the constant
+        assert_ok!(Bridge::set_proposal_lifetime(Origin::root(), 0));
+        assert_eq!(Bridge::stored_proposal_lifetime(), None);
+        assert_eq!(Bridge::effective_lifetime(src_id), ProposalLifetime::get());
+        assert_eq!(Bridge::effective_lifetime(2), ProposalLifetime::get());
+    })
+}
+
+#[test]
+fn proposal_expires() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Create proposal (& vote)
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Increment enough blocks such that now == expiry
+        System::set_block_number(ProposalLifetime::get() + 1);
+
+        // Attempt to submit a vote should fail
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalExpired
+        );
+
+        // Proposal state should remain unchanged
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // eval_vote_state should have no effect
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                SrcChainId(src_id),
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalExpired
+        );
+        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        assert_events(vec![Event::bridge(RawEvent::VoteFor(
+            src_id, prop_id, RELAYER_A, None,
+        ))]);
+    })
+}
+
+#[test]
+fn rejected_first_vote_creates_nothing() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+
+        // No vote fits, so neither the proposal nor its index and counters are stored
+        MaxRelayers::set(0);
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::TooManyRelayers
+        );
+        MaxRelayers::set(3);
+        assert_eq!(Bridge::total_proposals(), 0);
+        assert_eq!(Bridge::total_active_proposals(), 0);
+        assert!(!<ProposalKeys<Test>>::contains_key(
+            src_id,
+            (1, proposal.clone())
+        ));
+    })
+}
+
+#[test]
+fn vote_beyond_max_relayers() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Keep the proposal open while the full relayer set votes in favour
+        assert_ok!(Bridge::set_threshold(Origin::root(), 5));
+        for relayer in &[RELAYER_A, RELAYER_B, RELAYER_C] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+
+        // Rotate a relayer out; the new one cannot add a fourth vote
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_D));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_D),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::TooManyRelayers
+        );
+
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A, RELAYER_B, RELAYER_C].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+    })
+}
+
+#[test]
+fn last_approved_tracks_highest_nonce() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_eq!(Bridge::last_approved(src_id), None);
+
+        let approve = |nonce: DepositNonce, proposal: Call| {
+            for relayer in &[RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(*relayer),
+                    nonce,
+                    SrcChainId(src_id),
+                    r_id,
+                    Box::new(proposal.clone()),
+                    vec![]
+                ));
+            }
+        };
+
+        approve(2, make_proposal(vec![10]));
+        assert_eq!(Bridge::last_approved(src_id), Some(2));
+
+        // Executing an older nonce afterwards must not move the mark backwards
+        approve(1, make_proposal(vec![11]));
+        assert_eq!(Bridge::last_approved(src_id), Some(2));
+
+        approve(3, make_proposal(vec![12]));
+        assert_eq!(Bridge::last_approved(src_id), Some(3));
+    })
+}
+
+#[test]
+fn stale_nonces_rejected() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        RejectStaleNonces::set(true);
+
+        let approve = |nonce: DepositNonce, proposal: Call| {
+            for relayer in &[RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(*relayer),
+                    nonce,
+                    SrcChainId(src_id),
+                    r_id,
+                    Box::new(proposal.clone()),
+                    vec![]
+                ));
+            }
+        };
+
+        // Increasing nonces are accepted, gaps included
+        approve(1, make_proposal(vec![10]));
+        approve(3, make_proposal(vec![11]));
+        assert_eq!(Bridge::last_approved(src_id), Some(3));
+
+        for nonce in 1..=3 {
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    nonce,
+                    SrcChainId(src_id),
+                    r_id,
+                    Box::new(make_proposal(vec![12])),
+                    vec![]
+                ),
+                Error::<Test>::NonceAlreadyUsed
+            );
+        }
+
+        approve(4, make_proposal(vec![12]));
+        assert_eq!(Bridge::last_approved(src_id), Some(4));
+    })
+}
+
+#[test]
+fn force_reject_approvable_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        // The single vote is now enough to approve the proposal
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+
+        // Only the veto origin may force a rejection
+        assert_noop!(
+            Bridge::force_reject(
+                Origin::signed(RELAYER_A),
+                SrcChainId(src_id),
+                prop_id,
+                Box::new(proposal.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::force_reject(
+            Origin::signed(One::get()),
+            SrcChainId(src_id),
+            prop_id,
+            Box::new(proposal.clone())
+        ));
+
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Rejected);
+
+        // The proposal can no longer be executed
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn force_reject_missing_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_noop!(
+            Bridge::force_reject(
+                Origin::signed(One::get()),
+                SrcChainId(src_id),
+                1,
+                Box::new(make_proposal(vec![10]))
+            ),
+            Error::<Test>::ProposalDoesNotExist
+        );
+    })
+}
+
+#[test]
+fn vote_on_completed_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let approved = make_proposal(vec![10]);
+        let rejected = make_proposal(vec![11]);
+
+        // Approve the first proposal
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone()),
+                vec![]
+            ));
+        }
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyApproved
+        );
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_C),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone())
+            ),
+            Error::<Test>::ProposalAlreadyApproved
+        );
+
+        // Reject the second proposal
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::reject_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(rejected.clone())
+            ));
+        }
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(rejected.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyRejected
+        );
+    })
+}
+
+#[test]
+fn acknowledge_existing_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::set_threshold(Origin::root(), 3));
+
+        // The first vote creates the proposal, later votes are added to it
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.votes_for.to_vec(), vec![RELAYER_A, RELAYER_B]);
+        assert_eq!(Bridge::total_proposals(), 1);
+
+        // A proposal created by a vote can't be created again
+        assert_noop!(
+            Bridge::create_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyExists
+        );
+
+        let created = System::events()
+            .into_iter()
+            .filter(|record| matches!(record.event, Event::bridge(RawEvent::ProposalCreated(..))))
+            .count();
+        assert_eq!(created, 1);
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalCreated(
+                src_id,
+                prop_id,
+                Bridge::proposal_hash(&proposal),
+                vec![],
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B, None)),
+        ]);
+    })
+}
+
+#[test]
+fn resource_threshold() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let high_value = derive_resource_id(src_id, b"high value");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            high_value,
+            b"System.remark".to_vec()
+        ));
+        assert_noop!(
+            Bridge::set_resource_threshold(Origin::signed(RELAYER_A), high_value, 3),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_resource_threshold(
+            Origin::root(),
+            high_value,
+            3
+        ));
+        assert_eq!(Bridge::proposal_threshold(high_value), 3);
+        assert_eq!(Bridge::proposal_threshold(r_id), TEST_THRESHOLD);
+
+        // The global threshold of two is enough for other resources
+        let proposal = make_proposal(vec![10]);
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal.clone())).unwrap().status,
+            ProposalStatus::Approved
+        );
+
+        // The high value resource needs a third vote
+        let proposal = make_proposal(vec![11]);
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                high_value,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        assert_eq!(
+            Bridge::votes(src_id, (2, proposal.clone())).unwrap().status,
+            ProposalStatus::Initiated
+        );
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_C),
+            2,
+            SrcChainId(src_id),
+            high_value,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (2, proposal.clone())).unwrap().status,
+            ProposalStatus::Approved
+        );
+
+        // A resource threshold below the global one has no effect, and zero clears it
+        assert_ok!(Bridge::set_resource_threshold(
+            Origin::root(),
+            high_value,
+            1
+        ));
+        assert_eq!(Bridge::proposal_threshold(high_value), TEST_THRESHOLD);
+        assert_ok!(Bridge::set_resource_threshold(
+            Origin::root(),
+            high_value,
+            0
+        ));
+        assert_eq!(Bridge::resource_threshold(high_value), None);
+        assert_events(vec![Event::bridge(RawEvent::ResourceThresholdChanged(
+            high_value, 0,
+        ))]);
+    })
+}
+
+#[test]
+fn would_approve() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let outsider = 99;
+        assert!(Bridge::would_approve(
+            &[RELAYER_A, RELAYER_B],
+            TEST_THRESHOLD
+        ));
+        assert!(!Bridge::would_approve(&[RELAYER_A], TEST_THRESHOLD));
+
+        // Duplicates and non-relayers don't count towards the threshold
+        assert!(!Bridge::would_approve(
+            &[RELAYER_A, RELAYER_A],
+            TEST_THRESHOLD
+        ));
+        assert!(!Bridge::would_approve(
+            &[RELAYER_A, outsider],
+            TEST_THRESHOLD
+        ));
+        assert!(Bridge::would_approve(
+            &[outsider, RELAYER_A, RELAYER_A, RELAYER_C],
+            TEST_THRESHOLD
+        ));
+        assert!(!Bridge::would_approve(
+            &[RELAYER_A, RELAYER_B, RELAYER_B, outsider],
+            3
+        ));
+
+        // Relayers removed from the set no longer count
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+        assert!(!Bridge::would_approve(
+            &[RELAYER_A, RELAYER_B],
+            TEST_THRESHOLD
+        ));
+    })
+}
+
+#[test]
+fn create_proposal_then_vote() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_noop!(
+            Bridge::create_proposal(
+                Origin::signed(ENDOWED_BALANCE),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::MustBeRelayer
+        );
+
+        // Creating a proposal doesn't count as a vote
+        assert_ok!(Bridge::create_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        // A proposal can only be created once
+        assert_noop!(
+            Bridge::create_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyExists
+        );
+
+        // The creator is still free to vote, as are the other relayers
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        let expected = ProposalVotes {
+            votes_for: vec![RELAYER_A, RELAYER_B].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
+            status: ProposalStatus::Approved,
+            expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
+        };
+        assert_eq!(prop, expected);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn can_finalize() {
+    new_test_ext().execute_with(|| {
+        // Default threshold of 1 with no relayers
+        assert_eq!(Bridge::can_finalize(), false);
+
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::can_finalize(), true);
+
+        // Threshold above relayer count
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_eq!(Bridge::can_finalize(), false);
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::can_finalize(), true);
+
+        // Pausing overrides everything else
+        assert_noop!(
+            Bridge::set_paused(Origin::signed(RELAYER_A), true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_paused(Origin::root(), true));
+        assert_eq!(Bridge::can_finalize(), false);
+        assert_ok!(Bridge::set_paused(Origin::root(), false));
+        assert_eq!(Bridge::can_finalize(), true);
+
+        // Removing a relayer drops the count below threshold
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::can_finalize(), false);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::BridgePaused),
+            Event::bridge(RawEvent::BridgeUnpaused),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_B)),
+            Event::bridge(RawEvent::RelayerSetChanged(Bridge::relayer_set_hash())),
+        ]);
+    })
+}
+
+#[test]
+fn paused_bridge_rejects_transfers_and_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_ok!(Bridge::set_paused(Origin::root(), true));
+        assert_noop!(
+            Bridge::transfer_generic(src_id, r_id, vec![], None),
+            Error::<Test>::BridgeIsPaused
+        );
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::BridgeIsPaused
+        );
+        assert_noop!(
+            Bridge::create_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::BridgeIsPaused
+        );
+
+        assert_ok!(Bridge::set_paused(Origin::root(), false));
+        assert_ok!(Bridge::transfer_generic(src_id, r_id, vec![], None));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+    })
+}
+
+#[test]
+fn paused_chain_rejects_transfers_and_votes() {
+    let src_id = 1;
+    let other_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), other_id));
+
+        assert_noop!(
+            Bridge::set_chain_paused(Origin::signed(RELAYER_A), src_id, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_chain_paused(Origin::root(), src_id, true));
+        assert!(Bridge::is_chain_paused(src_id));
+        assert_noop!(
+            Bridge::transfer_generic(src_id, r_id, vec![], None),
+            Error::<Test>::ChainPaused
+        );
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ChainPaused
+        );
+
+        // Other chains are unaffected
+        let other_r_id = derive_resource_id(other_id, b"remark");
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            other_r_id,
+            b"System.remark".to_vec()
+        ));
+        assert_ok!(Bridge::transfer_generic(other_id, other_r_id, vec![], None));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(other_id),
+            other_r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        // A global pause takes precedence
+        assert_ok!(Bridge::set_paused(Origin::root(), true));
+        assert_noop!(
+            Bridge::transfer_generic(src_id, r_id, vec![], None),
+            Error::<Test>::BridgeIsPaused
+        );
+        assert_ok!(Bridge::set_paused(Origin::root(), false));
+
+        assert_ok!(Bridge::set_chain_paused(Origin::root(), src_id, false));
+        assert_ok!(Bridge::transfer_generic(src_id, r_id, vec![], None));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainUnpaused(src_id)),
+            Event::bridge(RawEvent::GenericTransfer(src_id, 1, r_id, vec![], None)),
+            Event::bridge(RawEvent::ProposalCreated(
+                src_id,
+                prop_id,
+                Bridge::proposal_hash(&proposal),
+                vec![],
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+        ]);
+    })
+}
+
+#[test]
+fn paused_resource_rejects_transfers_and_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let other_r_id = derive_resource_id(src_id, b"other");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            other_r_id,
+            b"System.remark".to_vec()
+        ));
+        let vote = |relayer, nonce, r_id| {
+            Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                nonce,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![],
+            )
+        };
+        assert_ok!(vote(RELAYER_A, 1, r_id));
+
+        assert_noop!(
+            Bridge::set_resource_paused(Origin::signed(RELAYER_A), r_id, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_resource_paused(Origin::root(), r_id, true));
+        assert!(Bridge::is_resource_paused(r_id));
+        assert_noop!(
+            Bridge::transfer_generic(src_id, r_id, vec![], None),
+            Error::<Test>::ResourcePaused
+        );
+        assert_noop!(vote(RELAYER_A, 2, r_id), Error::<Test>::ResourcePaused);
+        // Pending proposals of the resource can't be approved either
+        assert_noop!(vote(RELAYER_B, 1, r_id), Error::<Test>::ResourcePaused);
+
+        // Other resources are unaffected
+        assert_ok!(Bridge::transfer_generic(src_id, other_r_id, vec![], None));
+        assert_ok!(vote(RELAYER_A, 2, other_r_id));
+
+        assert_ok!(Bridge::set_resource_paused(Origin::root(), r_id, false));
+        assert_ok!(Bridge::transfer_generic(src_id, r_id, vec![], None));
+        assert_ok!(vote(RELAYER_B, 1, r_id));
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal.clone())).unwrap().status,
+            ProposalStatus::Approved
+        );
+        assert_events(vec![
+            Event::bridge(RawEvent::ResourceUnpaused(r_id)),
+            Event::bridge(RawEvent::GenericTransfer(src_id, 2, r_id, vec![], None)),
+            Event::bridge(RawEvent::VoteFor(src_id, 1, RELAYER_B, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, 1)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                1,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn transfer_generic_with_reply() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let reply_nonce = 42;
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::transfer_generic_with_reply(
+            dest_id,
+            resource_id,
+            vec![1, 2, 3],
+            reply_nonce
+        ));
+        assert_eq!(Bridge::pending_replies(dest_id, 1), Some(reply_nonce));
+        assert_events(vec![Event::bridge(RawEvent::GenericTransferWithReply(
+            dest_id,
+            1,
+            resource_id,
+            vec![1, 2, 3],
+            reply_nonce,
+        ))]);
+
+        // Replies must come from the destination chain and match the outbound nonce
+        assert_noop!(
+            Bridge::handle_reply(dest_id + 1, 1),
+            Error::<Test>::ReplyNotPending
+        );
+        assert_noop!(
+            Bridge::handle_reply(dest_id, 2),
+            Error::<Test>::ReplyNotPending
+        );
+
+        assert_eq!(Bridge::handle_reply(dest_id, 1), Ok(reply_nonce));
+        assert_eq!(Bridge::pending_replies(dest_id, 1), None);
+
+        // A reply can only be matched once
+        assert_noop!(
+            Bridge::handle_reply(dest_id, 1),
+            Error::<Test>::ReplyNotPending
+        );
+    })
+}
+
+#[test]
+fn vote_requires_signed_origin() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Neither root nor unsigned origins may vote, even though root is the admin
+        for origin in &[Origin::root(), Origin::none()] {
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    origin.clone(),
+                    prop_id,
+                    SrcChainId(src_id),
+                    r_id,
+                    Box::new(proposal.clone()),
+                    vec![]
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Bridge::reject_proposal(
+                    origin.clone(),
+                    prop_id,
+                    SrcChainId(src_id),
+                    r_id,
+                    Box::new(proposal.clone())
+                ),
+                DispatchError::BadOrigin
+            );
+        }
+        assert_eq!(Bridge::votes(src_id, (prop_id, proposal.clone())), None);
+    })
+}
+
+#[test]
+fn bridge_version_announced_once() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Bridge::bridge_version(), BRIDGE_VERSION);
+
+        Bridge::on_initialize(1);
+        assert_events(vec![Event::bridge(RawEvent::BridgeVersion(BRIDGE_VERSION))]);
+
+        System::reset_events();
+        Bridge::on_initialize(2);
+        assert!(System::events().is_empty());
+    })
+}
+
+#[test]
+fn resource_count() {
+    new_test_ext().execute_with(|| {
+        let ids = [[1; 32], [2; 32], [3; 32]];
+        assert_ok!(Bridge::whitelist_chains_with_nonce(
+            Origin::root(),
+            vec![(1, 0), (2, 0), (3, 0)]
+        ));
+        for id in &ids {
+            assert_ok!(Bridge::set_resource(
+                Origin::root(),
+                *id,
+                b"Example.method".to_vec()
+            ));
+        }
+        assert_eq!(Bridge::resource_count(), 3);
+
+        // Updating an existing resource doesn't change the count
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            ids[0],
+            b"Example.other".to_vec()
+        ));
+        assert_ok!(Bridge::set_resource_enabled(Origin::root(), ids[1], false));
+        assert_eq!(Bridge::resource_count(), 3);
+
+        assert_ok!(Bridge::remove_resource(Origin::root(), ids[2]));
+        assert_eq!(Bridge::resource_count(), 2);
+
+        // Removing an unknown resource doesn't change the count
+        assert_ok!(Bridge::remove_resource(Origin::root(), ids[2]));
+        assert_eq!(Bridge::resource_count(), 2);
+    })
+}
+
+#[test]
+fn inbound_rate_limit() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        InboundLimit::set(1);
+        let first = make_proposal(vec![10]);
+        let second = make_proposal(vec![11]);
+
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(first.clone()),
+                vec![]
+            ));
+        }
+        assert_eq!(
+            Bridge::votes(src_id, (1, first.clone())).unwrap().status,
+            ProposalStatus::Approved
+        );
+
+        // The second proposal has enough votes, but execution is deferred
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(second.clone()),
+                vec![]
+            ));
+        }
+        let prop = Bridge::votes(src_id, (2, second.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Initiated);
+        assert_eq!(prop.votes_for.len(), 2);
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_C),
+                2,
+                SrcChainId(src_id),
+                Box::new(second.clone())
+            ),
+            Error::<Test>::RateLimited
+        );
+
+        // Allowed again in the next window
+        System::set_block_number(INBOUND_WINDOW);
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_C),
+            2,
+            SrcChainId(src_id),
+            Box::new(second.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (2, second.clone())).unwrap().status,
+            ProposalStatus::Approved
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, 2, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteFor(src_id, 2, RELAYER_B, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, 2)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 2)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                2,
+                second.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn create_proposal_with_note() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let note = b"0xdeadbeef".to_vec();
+
+        assert_noop!(
+            Bridge::create_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![0; MaxProposalNoteLen::get() as usize + 1]
+            ),
+            Error::<Test>::NoteTooLong
+        );
+
+        assert_ok!(Bridge::create_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            note.clone()
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.note, note);
+        assert_events(vec![Event::bridge(RawEvent::ProposalCreated(
+            src_id,
+            prop_id,
+            Bridge::proposal_hash(&proposal),
+            note,
+        ))]);
+
+        // Proposals created by a vote have no note
+        let proposal = make_proposal(vec![11]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.note, Vec::<u8>::new());
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalCreated(
+                src_id,
+                prop_id,
+                Bridge::proposal_hash(&proposal),
+                vec![],
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+        ]);
+    })
+}
+
+#[test]
+fn reset_transfer_seq() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+
+        assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+        assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+        assert_eq!(Bridge::transfer_seq(), 2);
+
+        assert_noop!(
+            Bridge::reset_transfer_seq(Origin::signed(RELAYER_A), 100),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::reset_transfer_seq(Origin::root(), 100));
+        assert_eq!(Bridge::transfer_seq(), 100);
+
+        assert_ok!(Bridge::transfer_generic(dest_id, resource_id, vec![], None));
+        assert_eq!(Bridge::transfer_seq(), 101);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::TransferSeqReset(100)),
+            Event::bridge(RawEvent::GenericTransfer(
+                dest_id,
+                3,
+                resource_id,
+                vec![],
+                None,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn proposal_waits_for_quorum() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_noop!(
+            Bridge::set_quorum(Origin::signed(RELAYER_A), 3),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_quorum(Origin::root(), 3));
+        assert_eq!(Bridge::quorum(), 3);
+
+        // Two votes in favour meet the threshold, but not the quorum
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Initiated
+        );
+
+        // A vote against reaches the quorum, and the proposal is approved
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Approved
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_C, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn removed_resource_blocks_execution() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        MinConfirmations::set(5);
+        RejectRemovedResources::set(true);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        assert_ok!(Bridge::remove_resource(Origin::root(), r_id));
+
+        System::set_block_number(6);
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                SrcChainId(src_id),
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ResourceDoesNotExist
+        );
+
+        // Execution resumes once the resource is restored
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            b"System.remark".to_vec()
+        ));
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Approved
+        );
+    })
+}
+
+#[test]
+fn resource_method_mismatch() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert!(Bridge::resource_method_matches(r_id, &proposal));
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            b"System.set_heap_pages".to_vec()
+        ));
+        assert!(!Bridge::resource_method_matches(r_id, &proposal));
+
+        // Rejected by default instead of executed
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
+        ]);
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(Bridge::total_active_proposals(), 0);
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                SrcChainId(src_id),
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        MethodMismatch::set(MethodMismatchMode::LogAndProceed);
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id + 1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id + 1, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Approved
+        );
+        MethodMismatch::set(MethodMismatchMode::Reject);
+    })
+}
+
+#[test]
+fn resource_chain_mismatch() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let other_r_id = derive_resource_id(src_id, b"other");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            other_r_id,
+            b"System.remark".to_vec()
+        ));
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                other_r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ResourceChainMismatch
+        );
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                SrcChainId(src_id),
+                other_r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ResourceChainMismatch
+        );
+
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.resource_id, r_id);
+        assert_eq!(prop.votes_for.into_inner(), vec![RELAYER_A]);
+        assert_eq!(prop.status, ProposalStatus::Initiated);
+
+        // Resources of this chain are accepted, those of a third chain aren't
+        let local_r_id = derive_resource_id(TestChainId::get(), b"local");
+        let third_r_id = derive_resource_id(src_id + 1, b"third");
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id + 1));
+        for id in [local_r_id, third_r_id].iter() {
+            assert_ok!(Bridge::set_resource(
+                Origin::root(),
+                *id,
+                b"System.remark".to_vec()
+            ));
+        }
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id + 1,
+            SrcChainId(src_id),
+            local_r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id + 2,
+                SrcChainId(src_id),
+                third_r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::ResourceChainMismatch
+        );
+    })
+}
+
+#[test]
+fn max_total_active_proposals() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let other_src_id = 2;
+    let other_r_id = derive_resource_id(other_src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        MaxTotalActiveProposals::set(2);
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), other_src_id));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            other_r_id,
+            b"System.remark".to_vec()
+        ));
+
+        let vote = |relayer, nonce, src_id, r_id| {
+            Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                nonce,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![],
+            )
+        };
+
+        // The cap is shared between chains
+        assert_ok!(vote(RELAYER_A, 1, src_id, r_id));
+        assert_ok!(vote(RELAYER_A, 1, other_src_id, other_r_id));
+        assert_eq!(Bridge::total_active_proposals(), 2);
+        assert_noop!(
+            vote(RELAYER_A, 2, src_id, r_id),
+            Error::<Test>::TooManyActiveProposals
+        );
+        assert_noop!(
+            Bridge::create_proposal(
+                Origin::signed(RELAYER_A),
+                2,
+                SrcChainId(other_src_id),
+                other_r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::TooManyActiveProposals
+        );
+
+        // Votes on existing proposals are still accepted, and resolving one frees up a slot
+        assert_ok!(vote(RELAYER_B, 1, src_id, r_id));
+        assert_eq!(Bridge::total_active_proposals(), 1);
+        assert_ok!(vote(RELAYER_A, 2, src_id, r_id));
+        assert_eq!(Bridge::total_active_proposals(), 2);
+    })
+}
+
+#[test]
+fn proposal_resolution_hook() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+
+        // Not called for proposals that are still being voted on
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(ResolvedProposals::get(), vec![]);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::reject_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone())
+            ));
+        }
+
+        assert_eq!(
+            ResolvedProposals::get(),
+            vec![
+                (src_id, 1, r_id, ProposalStatus::Approved, true),
+                (src_id, 2, r_id, ProposalStatus::Rejected, false),
+            ]
+        );
+    })
+}
+
+#[test]
+fn proposal_resolution_hook_unexecuted() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let transfer_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            transfer_id,
+            b"Balances.transfer".to_vec()
+        ));
+        let proposal = make_proposal(vec![10]);
+
+        // Approved, but the call fails when dispatched
+        let overdraw = Call::Balances(mock::balances::Call::transfer(
+            RELAYER_A,
+            ENDOWED_BALANCE * 2,
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            transfer_id,
+            Box::new(overdraw.clone()),
+            vec![]
+        ));
+        assert_err!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                1,
+                SrcChainId(src_id),
+                transfer_id,
+                Box::new(overdraw.clone()),
+                vec![]
+            ),
+            mock::balances::Error::<Test, _>::InsufficientBalance
+        );
+
+        // Vetoed
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_ok!(Bridge::force_reject(
+            Origin::signed(One::get()),
+            SrcChainId(src_id),
+            2,
+            Box::new(proposal.clone())
+        ));
+
+        // Expired
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            3,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        System::set_block_number(ProposalLifetime::get() + 2);
+        assert_ok!(Bridge::expire_proposal(
+            Origin::signed(ENDOWED_BALANCE),
+            SrcChainId(src_id),
+            3,
+            Box::new(proposal.clone())
+        ));
+
+        assert_eq!(
+            ResolvedProposals::get(),
+            vec![
+                (src_id, 1, transfer_id, ProposalStatus::Approved, false),
+                (src_id, 2, r_id, ProposalStatus::Rejected, false),
+                (src_id, 3, r_id, ProposalStatus::Rejected, false),
+            ]
+        );
+    })
+}
+
+#[test]
+fn proposal_filtered_at_execution() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        MinConfirmations::set(5);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Allowed while voting
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+
+        FilterRemarks::set(true);
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id + 1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(make_proposal(vec![11])),
+                vec![]
+            ),
+            Error::<Test>::ProposalFiltered
+        );
+
+        System::set_block_number(6);
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Approved
+        );
+        assert_eq!(Bridge::last_approved(src_id), None);
+        assert_eq!(
+            ResolvedProposals::get(),
+            vec![(src_id, prop_id, r_id, ProposalStatus::Approved, false)]
+        );
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalFilteredAtExecution(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalFailed(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn reject_creates_proposal_configurable() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let first = make_proposal(vec![10]);
+        let second = make_proposal(vec![11]);
+
+        // By default a vote against creates the proposal
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(first.clone())
+        ));
+        let prop = Bridge::votes(src_id, (1, first.clone())).unwrap();
+        assert_eq!(prop.votes_against.to_vec(), vec![RELAYER_A]);
+
+        AllowRejectCreatesProposal::set(false);
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_A),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(second.clone())
+            ),
+            Error::<Test>::ProposalDoesNotExist
+        );
+
+        // Existing proposals can still be voted against
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(first.clone())
+        ));
+        let prop = Bridge::votes(src_id, (1, first.clone())).unwrap();
+        assert_eq!(prop.votes_against.to_vec(), vec![RELAYER_A, RELAYER_B]);
+    })
+}
+
+#[test]
+fn proposals_by_status() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let initiated = make_proposal(vec![10]);
+        let approved = make_proposal(vec![11]);
+        let rejected = make_proposal(vec![12]);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(initiated.clone()),
+            vec![]
+        ));
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone()),
+                vec![]
+            ));
+            assert_ok!(Bridge::reject_proposal(
+                Origin::signed(*relayer),
+                3,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(rejected.clone())
+            ));
+        }
+
+        assert_eq!(
+            Bridge::proposals_by_status(src_id, ProposalStatus::Initiated),
+            vec![1]
+        );
+        assert_eq!(
+            Bridge::proposals_by_status(src_id, ProposalStatus::Approved),
+            vec![2]
+        );
+        assert_eq!(
+            Bridge::proposals_by_status(src_id, ProposalStatus::Rejected),
+            vec![3]
+        );
+        assert_eq!(
+            Bridge::proposals_by_status(src_id + 1, ProposalStatus::Initiated),
+            Vec::<DepositNonce>::new()
+        );
+    })
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn votes_storage_key() {
+    new_test_ext().execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        for (src_id, nonce) in &[(0, 0), (1, 1), (2, 100)] {
+            assert_eq!(
+                Bridge::votes_storage_key(*src_id, *nonce, &proposal),
+                <Votes<Test>>::hashed_key_for(src_id, (nonce, proposal.clone()))
+            );
+        }
+    })
+}
+
+#[test]
+fn proposal_waits_for_confirmations() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        MinConfirmations::set(5);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.created_at, 1);
+        assert_eq!(prop.status, ProposalStatus::Initiated);
+
+        System::set_block_number(5);
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                SrcChainId(src_id),
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::AwaitingConfirmations
+        );
+
+        System::set_block_number(6);
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            SrcChainId(src_id),
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Approved
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn duplicate_vote_attempt() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        // Not reported unless enabled
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::RelayerAlreadyVoted
+        );
+
+        ReportDuplicateVotes::set(true);
+        assert_err!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::RelayerAlreadyVoted
+        );
+        assert_events(vec![Event::bridge(RawEvent::DuplicateVoteAttempt(
+            src_id, prop_id, RELAYER_A,
+        ))]);
+    })
+}
+
+#[test]
+fn proposal_dispatch_class_follows_inner_call() {
+    let src_id = SrcChainId(1);
+    let r_id = derive_resource_id(1, b"remark");
+    let operational = Call::System(system::Call::set_heap_pages(64));
+    let normal = make_proposal(vec![10]);
+    assert_eq!(
+        operational.get_dispatch_info().class,
+        DispatchClass::Operational
+    );
+
+    for (inner, class) in [
+        (operational, DispatchClass::Operational),
+        (normal, DispatchClass::Normal),
+    ]
+    .iter()
+    .cloned()
+    {
+        let acknowledge = Call::Bridge(crate::Call::acknowledge_proposal(
+            1,
+            src_id,
+            r_id,
+            Box::new(inner.clone()),
+            vec![],
+        ));
+        assert_eq!(acknowledge.get_dispatch_info().class, class);
+
+        let eval = Call::Bridge(crate::Call::eval_vote_state(1, src_id, Box::new(inner)));
+        assert_eq!(eval.get_dispatch_info().class, class);
+    }
+}
+
+#[test]
+fn proposal_weight_scales_with_call_size() {
+    let src_id = SrcChainId(1);
+    let r_id = derive_resource_id(1, b"remark");
+    // Weight of acknowledging the proposal, excluding the weight of the call itself
+    let vote_weight = |inner: mock::Call| {
+        let call_weight = inner.get_dispatch_info().weight;
+        let acknowledge = Call::Bridge(crate::Call::acknowledge_proposal(
+            1,
+            src_id,
+            r_id,
+            Box::new(inner),
+            vec![],
+        ));
+        acknowledge.get_dispatch_info().weight - call_weight
+    };
+
+    let small = vote_weight(make_proposal(vec![0; 10]));
+    let large = vote_weight(make_proposal(vec![0; 10_000]));
+    assert!(large > small);
+    assert_eq!(
+        small,
+        <() as WeightInfo>::acknowledge_proposal(make_proposal(vec![0; 10]).encoded_size() as u32)
+    );
+}
+
+#[test]
+fn rotate_committee() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        RelayerActivationDelay::set(5);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        assert_noop!(
+            Bridge::rotate_committee(Origin::signed(RELAYER_A), vec![RELAYER_A], 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::rotate_committee(Origin::root(), vec![RELAYER_A, RELAYER_D], 3),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_noop!(
+            Bridge::rotate_committee(Origin::root(), vec![RELAYER_A, RELAYER_D], 0),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_noop!(
+            Bridge::rotate_committee(Origin::root(), vec![RELAYER_A, RELAYER_A], 2),
+            Error::<Test>::RelayerAlreadyExists
+        );
+        let too_many: Vec<u64> = (0..=MaxBatchSize::get() as u64).map(|i| 10 + i).collect();
+        assert_noop!(
+            Bridge::rotate_committee(Origin::root(), too_many, 1),
+            Error::<Test>::BatchTooLarge
+        );
+
+        assert_ok!(Bridge::rotate_committee(
+            Origin::root(),
+            vec![RELAYER_A, RELAYER_D],
+            2
+        ));
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(!Bridge::is_relayer(&RELAYER_B));
+        assert!(!Bridge::is_relayer(&RELAYER_C));
+        assert!(Bridge::is_relayer(&RELAYER_D));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_eq!(Bridge::relayer_threshold(), 2);
+        assert_eq!(Bridge::relayer_active_from(RELAYER_A), Some(1));
+        assert_eq!(Bridge::relayer_active_from(RELAYER_D), Some(6));
+        assert_eq!(Bridge::relayer_active_from(RELAYER_B), None);
+
+        // In-flight proposals are kept
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.votes_for.to_vec(), vec![RELAYER_A]);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerSetReplaced(vec![RELAYER_A, RELAYER_D])),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_A, RELAYER_D,
+            ]))),
+            Event::bridge(RawEvent::RelayerThresholdChanged(2)),
+        ]);
+    })
+}
+
+#[test]
+fn rotate_committee_removes_unlisted_relayers() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        // A relayer added without being recorded in `RelayerList`
+        let unlisted = 99;
+        <Relayers<Test>>::insert(unlisted, true);
+        assert!(!Bridge::relayer_list().contains(&unlisted));
+
+        assert_ok!(Bridge::rotate_committee(
+            Origin::root(),
+            vec![RELAYER_A, RELAYER_B],
+            2
+        ));
+        assert!(!Bridge::is_relayer(&unlisted));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(unlisted),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(make_proposal(vec![10])),
+                vec![]
+            ),
+            Error::<Test>::MustBeRelayer
+        );
+    })
+}
+
+#[test]
+fn emergency_reset() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        // Only root may reset, even if it differs from the admin origin
+        assert_noop!(
+            Bridge::emergency_reset(Origin::signed(RELAYER_A), vec![RELAYER_D], 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::emergency_reset(Origin::root(), vec![RELAYER_D], 0),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_noop!(
+            Bridge::emergency_reset(Origin::root(), vec![RELAYER_D, RELAYER_D], 1),
+            Error::<Test>::RelayerAlreadyExists
+        );
+        assert_noop!(
+            Bridge::emergency_reset(
+                Origin::root(),
+                vec![RELAYER_A, RELAYER_B, RELAYER_C, RELAYER_D],
+                1
+            ),
+            Error::<Test>::TooManyRelayers
+        );
+        let too_many: Vec<u64> = (0..=MaxBatchSize::get() as u64).map(|i| 10 + i).collect();
+        assert_noop!(
+            Bridge::emergency_reset(Origin::root(), too_many, 1),
+            Error::<Test>::BatchTooLarge
+        );
+
+        assert_ok!(Bridge::emergency_reset(
+            Origin::root(),
+            vec![RELAYER_C, RELAYER_D],
+            1
+        ));
+        assert!(!Bridge::is_relayer(&RELAYER_A));
+        assert!(!Bridge::is_relayer(&RELAYER_B));
+        assert!(Bridge::is_relayer(&RELAYER_C));
+        assert!(Bridge::is_relayer(&RELAYER_D));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_eq!(Bridge::relayer_threshold(), 1);
+        assert_eq!(Bridge::votes(src_id, (prop_id, proposal.clone())), None);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerSetReplaced(vec![RELAYER_C, RELAYER_D])),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_C, RELAYER_D,
+            ]))),
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+        ]);
+
+        // The purged proposal can be voted on from scratch by the new set
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_D),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, proposal.clone()))
+                .unwrap()
+                .status,
+            ProposalStatus::Approved
+        );
+    })
+}
+
+#[test]
+fn drop_undecodable_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        // Seed corrupt entries for this chain and another one
+        let corrupt_key = |chain: ChainId, junk: &[u8]| {
+            let mut key = <Votes<Test>>::final_prefix().to_vec();
+            key.extend_from_slice(&chain.using_encoded(sp_io::hashing::blake2_256));
+            key.extend_from_slice(&sp_io::hashing::blake2_256(junk));
+            key
+        };
+        // and the index entries of the same proposals, which were counted as active
+        let index_key = |chain: ChainId, junk: &[u8]| {
+            let mut key = <ProposalKeys<Test>>::final_prefix().to_vec();
+            key.extend_from_slice(&chain.using_encoded(sp_io::hashing::blake2_128));
+            key.extend_from_slice(&chain.encode());
+            key.extend_from_slice(&sp_io::hashing::blake2_128(junk));
+            key.extend_from_slice(junk);
+            key
+        };
+        for junk in &[b"one", b"two"] {
+            frame_support::storage::unhashed::put_raw(&corrupt_key(src_id, *junk), &[0xff; 3]);
+            frame_support::storage::unhashed::put_raw(&index_key(src_id, *junk), &[]);
+        }
+        frame_support::storage::unhashed::put_raw(&corrupt_key(src_id + 1, b"one"), &[0xff; 3]);
+        <TotalActiveProposals>::put(3);
+
+        assert_noop!(
+            Bridge::drop_undecodable_votes(Origin::signed(RELAYER_A), src_id, 10),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Bridge::drop_undecodable_votes(Origin::root(), src_id, 1));
+        assert_events(vec![Event::bridge(RawEvent::UndecodableVotesDropped(
+            src_id, 1,
+        ))]);
+        assert_ok!(Bridge::drop_undecodable_votes(Origin::root(), src_id, 10));
+        assert_events(vec![Event::bridge(RawEvent::UndecodableVotesDropped(
+            src_id, 1,
+        ))]);
+
+        for junk in &[b"one", b"two"] {
+            assert_eq!(
+                frame_support::storage::unhashed::get_raw(&corrupt_key(src_id, *junk)),
+                None
+            );
+            assert_eq!(
+                frame_support::storage::unhashed::get_raw(&index_key(src_id, *junk)),
+                None
+            );
+        }
+        assert_eq!(Bridge::total_active_proposals(), 1);
+        // Valid proposals and other chains are untouched
+        assert!(Bridge::votes(src_id, (1, proposal.clone())).is_some());
+        assert!(<ProposalKeys<Test>>::contains_key(
+            src_id,
+            (1, proposal.clone())
+        ));
+        assert!(
+            frame_support::storage::unhashed::get_raw(&corrupt_key(src_id + 1, b"one")).is_some()
+        );
+    })
+}
+
+#[test]
+fn runtime_upgrade_migrates_resources_and_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext().execute_with(|| {
+        PalletVersion::new(0, 0, 2)
+            .put_into_storage::<<Test as frame_system::Config>::PalletInfo, Bridge>();
+        let proposal = make_proposal(vec![10]);
+        let method = b"System.remark".to_vec();
+
+        // Seed values in the layout of version 0.0.2
+        frame_support::storage::unhashed::put(&<Resources>::hashed_key_for(r_id), &method);
+        frame_support::storage::unhashed::put(
+            &<Votes<Test>>::hashed_key_for(src_id, (1, proposal.clone())),
+            &(
+                vec![RELAYER_A],
+                Vec::<u64>::new(),
+                ProposalStatus::Initiated,
+                60u64,
+            ),
+        );
+        let too_many: Vec<u64> = (0..=MaxRelayers::get() as u64).collect();
+        frame_support::storage::unhashed::put(
+            &<Votes<Test>>::hashed_key_for(src_id, (2, proposal.clone())),
+            &(
+                too_many,
+                Vec::<u64>::new(),
+                ProposalStatus::Initiated,
+                60u64,
+            ),
+        );
+
+        Bridge::on_runtime_upgrade();
+        assert_eq!(Bridge::storage_version(), Some(Bridge::current_version()));
+        assert_eq!(Bridge::resources(r_id), Some((method.clone(), true)));
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal.clone())),
+            Some(ProposalVotes {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: Default::default(),
+                status: ProposalStatus::Initiated,
+                expiry: 60,
+                resource_id: [0; 32],
+                note: vec![],
+                created_at: 60 - ProposalLifetime::get(),
+            })
+        );
+        assert_eq!(Bridge::votes(src_id, (2, proposal.clone())), None);
+
+        // The next vote records the resource of a migrated proposal
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (1, proposal.clone()))
+                .unwrap()
+                .resource_id,
+            r_id
+        );
+
+        // Values already in the new layout are left alone
+        assert_ok!(Bridge::set_resource_enabled(Origin::root(), r_id, false));
+        Bridge::on_runtime_upgrade();
+        assert_eq!(Bridge::resources(r_id), Some((method, false)));
+    })
+}
+
+#[test]
+fn logged_state_transitions() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    // Logs aren't captured, this only runs each instrumented path
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let approved = make_proposal(vec![10]);
+        let rejected = make_proposal(vec![11]);
+        let expired = make_proposal(vec![12]);
+
+        assert_ok!(Bridge::create_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(approved.clone()),
+            vec![]
+        ));
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone()),
+                vec![]
+            ));
+            assert_ok!(Bridge::reject_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(rejected.clone())
+            ));
+        }
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            3,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(expired.clone()),
+            vec![]
+        ));
+        System::set_block_number(ProposalLifetime::get() + 2);
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                3,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(expired.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalExpired
+        );
+
+        assert_ok!(Bridge::set_threshold(Origin::root(), 3));
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerThresholdChanged(3)),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_C)),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_A, RELAYER_B,
+            ]))),
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_C)),
+            Event::bridge(RawEvent::RelayerSetChanged(set_hash(&[
+                RELAYER_A, RELAYER_B, RELAYER_C,
+            ]))),
+        ]);
+    })
+}
+
+#[test]
+fn proposal_records_creation_block() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        assert_eq!(
+            Bridge::proposal_created_at(src_id, prop_id, proposal.clone()),
+            None
+        );
+
+        System::set_block_number(7);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        // Later votes don't move the creation block
+        System::set_block_number(9);
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_created_at(src_id, prop_id, proposal.clone()),
+            Some(7)
+        );
+    })
+}
+
+#[test]
+fn total_proposals_counts_new_proposals() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let first = make_proposal(vec![10]);
+        let second = make_proposal(vec![11]);
+        assert_eq!(Bridge::total_proposals(), 0);
+
+        assert_ok!(Bridge::create_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(first.clone()),
+            vec![]
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(second.clone()),
+            vec![]
+        ));
+        // Voting on an existing proposal doesn't count as a new one
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            2,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(second.clone())
+        ));
+
+        assert_eq!(Bridge::total_proposals(), 2);
+    })
+}
+
+#[test]
+fn vote_proof_optional() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let proof = b"source-tx-receipt".to_vec();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            proof.clone()
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        assert_eq!(
+            Bridge::vote_proofs(src_id, (prop_id, proposal.clone(), RELAYER_A)),
+            Some(proof)
+        );
+        assert_eq!(
+            Bridge::vote_proofs(src_id, (prop_id, proposal.clone(), RELAYER_B)),
+            None
+        );
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![0; MaxVoteProofLen::get() as usize + 1]
+            ),
+            Error::<Test>::ProofTooLong
+        );
+    })
+}
+
+#[test]
+fn vote_proof_required() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        RequireVoteProof::set(true);
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let proof = b"source-tx-receipt".to_vec();
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::MissingProof
+        );
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            proof.clone()
+        ));
+        assert_eq!(
+            Bridge::vote_proofs(src_id, (prop_id, proposal.clone(), RELAYER_A)),
+            Some(proof)
+        );
+        assert_events(vec![Event::bridge(RawEvent::VoteFor(
+            src_id, prop_id, RELAYER_A, None,
+        ))]);
+    })
+}
+
+#[test]
+fn relayer_heartbeat() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), None);
+        assert_noop!(
+            Bridge::heartbeat(Origin::signed(1)),
+            Error::<Test>::MustBeRelayer
+        );
+
+        System::set_block_number(5);
+        assert_ok!(Bridge::heartbeat(Origin::signed(RELAYER_A)));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), Some(5));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_B), None);
+        assert_events(vec![Event::bridge(RawEvent::RelayerHeartbeat(
+            RELAYER_A, 5,
+        ))]);
+
+        System::set_block_number(8);
+        assert_ok!(Bridge::heartbeat(Origin::signed(RELAYER_A)));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), Some(8));
+
+        // Removed relayers no longer have a heartbeat
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), None);
+    })
+}
+
+#[test]
+fn unregister_relayer_prunes_heartbeat() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::heartbeat(Origin::signed(RELAYER_A)));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), Some(1));
+
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), None);
+
+        // Re-adding the relayer doesn't bring back its old heartbeat
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::last_heartbeat(RELAYER_A), None);
+    })
+}
+
+#[test]
+fn assign_relayer_chain() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_noop!(
+            Bridge::assign_relayer_chain(Origin::signed(RELAYER_A), RELAYER_A, 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::assign_relayer_chain(Origin::root(), RELAYER_B, 1),
+            Error::<Test>::RelayerInvalid
+        );
+
+        // The chain is left as is by default
+        assert_ok!(Bridge::assign_relayer_chain(Origin::root(), RELAYER_A, 1));
+        assert!(Bridge::relayer_chain_assigned(RELAYER_A, 1));
+        assert!(!Bridge::chain_whitelisted(1));
+        assert_events(vec![Event::bridge(RawEvent::RelayerChainAssigned(
+            RELAYER_A, 1,
+        ))]);
+
+        // Assignments are dropped with the relayer
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert!(!Bridge::relayer_chain_assigned(RELAYER_A, 1));
+    })
+}
+
+#[test]
+fn assigned_relayers_vote_only_from_their_chains() {
+    let src_id = 1;
+    let other_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), other_id));
+        assert_ok!(Bridge::assign_relayer_chain(
+            Origin::root(),
+            RELAYER_A,
+            other_id
+        ));
+        let proposal = make_proposal(vec![10]);
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::RelayerNotAssignedToChain
+        );
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::RelayerNotAssignedToChain
+        );
+
+        // Relayers without assignments may vote from any chain
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+
+        assert_ok!(Bridge::assign_relayer_chain(
+            Origin::root(),
+            RELAYER_A,
+            src_id
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+    })
+}
+
+#[test]
+fn assign_relayer_chain_auto_whitelist() {
+    new_test_ext().execute_with(|| {
+        AutoWhitelistOnRelayerAssign::set(true);
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
-        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
-        assert_eq!(Bridge::relayer_count(), 3);
 
-        // Already exists
+        assert_ok!(Bridge::assign_relayer_chain(Origin::root(), RELAYER_A, 1));
+        assert!(Bridge::chain_whitelisted(1));
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainWhitelisted(1, None)),
+            Event::bridge(RawEvent::RelayerChainAssigned(RELAYER_A, 1)),
+        ]);
+
+        // Already whitelisted chains are kept as they are
+        assert_ok!(Bridge::assign_relayer_chain(Origin::root(), RELAYER_B, 1));
+        assert_events(vec![Event::bridge(RawEvent::RelayerChainAssigned(
+            RELAYER_B, 1,
+        ))]);
+        assert_eq!(WhitelistedChains::get(), vec![1]);
+
         assert_noop!(
-            Bridge::add_relayer(Origin::root(), RELAYER_A),
-            Error::<Test>::RelayerAlreadyExists
+            Bridge::assign_relayer_chain(Origin::root(), RELAYER_A, TestChainId::get()),
+            Error::<Test>::InvalidChainId
+        );
+    })
+}
+
+#[test]
+fn completed_proposals_cannot_be_reproposed() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let approved = make_proposal(vec![10]);
+        let rejected = make_proposal(vec![11]);
+        assert_eq!(Bridge::proposal_outcome(src_id, 1), None);
+
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone()),
+                vec![]
+            ));
+            assert_ok!(Bridge::reject_proposal(
+                Origin::signed(*relayer),
+                2,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(rejected.clone())
+            ));
+        }
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            3,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(approved.clone()),
+            vec![]
+        ));
+        assert_eq!(
+            Bridge::proposal_outcome(src_id, 1),
+            Some(ProposalStatus::Approved)
+        );
+        assert_eq!(
+            Bridge::proposal_outcome(src_id, 2),
+            Some(ProposalStatus::Rejected)
         );
+        assert_eq!(Bridge::proposal_outcome(src_id, 3), None);
 
-        // Confirm removal
-        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
-        assert_eq!(Bridge::relayer_count(), 2);
+        // Purge all active proposals
+        assert_ok!(Bridge::emergency_reset(
+            Origin::root(),
+            vec![RELAYER_A, RELAYER_B, RELAYER_C],
+            TEST_THRESHOLD
+        ));
+        assert_eq!(Bridge::votes(src_id, (1, approved.clone())), None);
+        assert_eq!(<ProposalKeys<Test>>::iter_prefix(src_id).count(), 0);
+        assert_eq!(Bridge::total_active_proposals(), 0);
+
+        for nonce in 1..=2 {
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    nonce,
+                    SrcChainId(src_id),
+                    r_id,
+                    Box::new(approved.clone()),
+                    vec![]
+                ),
+                Error::<Test>::ProposalAlreadyComplete
+            );
+        }
+        // Unresolved nonces can be proposed again
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            3,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(approved.clone()),
+            vec![]
+        ));
+    })
+}
+
+#[test]
+fn completed_nonce_rejects_other_proposals() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let approved = make_proposal(vec![10]);
+        let other = make_proposal(vec![11]);
+
+        // Both proposals are created for the same nonce before either is resolved
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(other.clone()),
+            vec![]
+        ));
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(approved.clone()),
+                vec![]
+            ));
+        }
+        assert_eq!(
+            Bridge::proposal_outcome(src_id, 1),
+            Some(ProposalStatus::Approved)
+        );
+
+        // The other proposal still exists, but can't be voted on once the nonce is resolved
         assert_noop!(
-            Bridge::remove_relayer(Origin::root(), RELAYER_B),
-            Error::<Test>::RelayerInvalid
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(other.clone()),
+                vec![]
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        // Vetoing it doesn't replace the approval
+        assert_ok!(Bridge::force_reject(
+            Origin::signed(One::get()),
+            SrcChainId(src_id),
+            1,
+            Box::new(other.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_outcome(src_id, 1),
+            Some(ProposalStatus::Approved)
+        );
+    })
+}
+
+#[test]
+fn stake_weighted_approval() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        StakeThreshold::set(Some(Perbill::from_percent(60)));
+        RelayerStakes::set(
+            vec![(RELAYER_A, 70), (RELAYER_B, 15), (RELAYER_C, 15)]
+                .into_iter()
+                .collect(),
         );
-        assert_eq!(Bridge::relayer_count(), 2);
+        let proposal = make_proposal(vec![10]);
 
+        // The other relayers together don't hold enough stake to approve
+        for relayer in &[RELAYER_B, RELAYER_C] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
+        let prop = Bridge::votes(src_id, (1, proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Initiated);
+
+        // The large staker alone meets the fraction
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        let prop = Bridge::votes(src_id, (2, proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Approved);
         assert_events(vec![
-            Event::bridge(RawEvent::RelayerAdded(RELAYER_A)),
-            Event::bridge(RawEvent::RelayerAdded(RELAYER_B)),
-            Event::bridge(RawEvent::RelayerAdded(RELAYER_C)),
-            Event::bridge(RawEvent::RelayerRemoved(RELAYER_B)),
+            Event::bridge(RawEvent::ProposalCreated(
+                src_id,
+                2,
+                Bridge::proposal_hash(&proposal),
+                vec![],
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, 2, RELAYER_A, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, 2)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 2)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                2,
+                proposal.get_dispatch_info().weight,
+            )),
         ]);
     })
 }
 
-fn make_proposal(r: Vec<u8>) -> mock::Call {
-    Call::System(system::Call::remark(r))
-}
-
 #[test]
-fn create_sucessful_proposal() {
+fn new_relayer_waits_for_activation() {
     let src_id = 1;
     let r_id = derive_resource_id(src_id, b"remark");
 
     new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
-        let prop_id = 1;
+        RelayerActivationDelay::set(5);
+        let relayer = 0x5;
         let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        assert_eq!(Bridge::relayer_active_from(relayer), Some(6));
 
-        // Create proposal (& vote)
+        // Relayers added before the delay was set are unaffected
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
-            prop_id,
-            src_id,
+            1,
+            SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
 
-        // Second relayer votes against
-        assert_ok!(Bridge::reject_proposal(
-            Origin::signed(RELAYER_B),
-            prop_id,
-            src_id,
-            r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+        System::set_block_number(5);
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            Error::<Test>::RelayerNotYetActive
+        );
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(relayer),
+                1,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::RelayerNotYetActive
+        );
 
-        // Third relayer votes in favour
+        System::set_block_number(6);
         assert_ok!(Bridge::acknowledge_proposal(
-            Origin::signed(RELAYER_C),
-            prop_id,
-            src_id,
+            Origin::signed(relayer),
+            1,
+            SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A, RELAYER_C],
-            votes_against: vec![RELAYER_B],
-            status: ProposalStatus::Approved,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
-
         assert_events(vec![
-            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
-            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
-            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_C)),
-            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
-            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::VoteFor(src_id, 1, relayer, None)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, 1)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                1,
+                proposal.get_dispatch_info().weight,
+            )),
         ]);
     })
 }
 
 #[test]
-fn create_unsucessful_proposal() {
+fn first_votes_in_same_block_share_proposal() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"transfer");
+    let r_id = derive_resource_id(src_id, b"remark");
 
     new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_ok!(Bridge::set_threshold(Origin::root(), 3));
         let prop_id = 1;
-        let proposal = make_proposal(vec![11]);
+        let proposal = make_proposal(vec![10]);
 
-        // Create proposal (& vote)
-        assert_ok!(Bridge::acknowledge_proposal(
-            Origin::signed(RELAYER_A),
-            prop_id,
-            src_id,
-            r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+        // Both relayers find no existing proposal for the nonce
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                prop_id,
+                SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ));
+        }
 
-        // Second relayer votes against
-        assert_ok!(Bridge::reject_proposal(
-            Origin::signed(RELAYER_B),
-            prop_id,
-            src_id,
-            r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.votes_for.to_vec(), vec![RELAYER_A, RELAYER_B]);
+        assert_eq!(Bridge::total_proposals(), 1);
+    })
+}
 
-        // Third relayer votes against
-        assert_ok!(Bridge::reject_proposal(
-            Origin::signed(RELAYER_C),
-            prop_id,
-            src_id,
-            r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B, RELAYER_C],
-            status: ProposalStatus::Rejected,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+#[test]
+fn relayers_status() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Bridge::relayers_status(), vec![]);
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
 
-        assert_eq!(Balances::free_balance(RELAYER_B), 0);
+        System::set_block_number(4);
+        assert_ok!(Bridge::heartbeat(Origin::signed(RELAYER_B)));
         assert_eq!(
-            Balances::free_balance(Bridge::account_id()),
-            ENDOWED_BALANCE
+            Bridge::relayers_status(),
+            vec![(RELAYER_A, None), (RELAYER_B, Some(4)), (RELAYER_C, None)]
         );
 
-        assert_events(vec![
-            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
-            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
-            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_C)),
-            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
-        ]);
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(
+            Bridge::relayers_status(),
+            vec![(RELAYER_B, Some(4)), (RELAYER_C, None)]
+        );
     })
 }
 
 #[test]
-fn execute_after_threshold_change() {
+fn approval_hook_weight_reported() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"transfer");
+    let r_id = derive_resource_id(src_id, b"remark");
 
     new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
-        let prop_id = 1;
-        let proposal = make_proposal(vec![11]);
+        ApprovalHookWeight::set(1_000);
+        let proposal = make_proposal(vec![10]);
+        let weight = proposal.get_dispatch_info().weight
+            + <() as WeightInfo>::acknowledge_proposal(proposal.encoded_size() as u32);
 
-        // Create proposal (& vote)
-        assert_ok!(Bridge::acknowledge_proposal(
+        // Only the vote that executes the proposal includes the hook's weight
+        let first = Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
-            prop_id,
-            src_id,
+            1,
+            SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+            Box::new(proposal.clone()),
+            vec![],
+        );
+        assert_eq!(first.unwrap().actual_weight, Some(weight));
 
-        // Change threshold
-        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        let second = Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![],
+        );
+        assert_eq!(second.unwrap().actual_weight, Some(weight + 1_000));
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalApproved(src_id, 1)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::ProposalExecuted(
+                src_id,
+                1,
+                proposal.get_dispatch_info().weight,
+            )),
+        ]);
+    })
+}
 
-        // Attempt to execute
-        assert_ok!(Bridge::eval_vote_state(
+#[test]
+fn proposal_executed_reports_actual_weight() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let base = <() as WeightInfo>::acknowledge_proposal(proposal.encoded_size() as u32);
+        assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
-            prop_id,
-            src_id,
-            Box::new(proposal.clone())
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![],
         ));
 
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Approved,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
-
-        assert_eq!(Balances::free_balance(RELAYER_B), 0);
-        assert_eq!(
-            Balances::free_balance(Bridge::account_id()),
-            ENDOWED_BALANCE
+        let result = Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![],
         );
 
-        assert_events(vec![
-            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
-            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
-            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
-            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
-        ]);
+        let executed = System::events()
+            .into_iter()
+            .find_map(|record| match record.event {
+                Event::bridge(RawEvent::ProposalExecuted(_, _, weight)) => Some(weight),
+                _ => None,
+            })
+            .expect("ProposalExecuted expected");
+        assert!(executed > 0);
+        assert_eq!(result.unwrap().actual_weight, Some(base + executed));
     })
 }
 
 #[test]
-fn proposal_expires() {
+fn proposal_with_empty_relayer_set_expires() {
     let src_id = 1;
     let r_id = derive_resource_id(src_id, b"remark");
 
     new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
         let prop_id = 1;
         let proposal = make_proposal(vec![10]);
-
-        // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
             prop_id,
-            src_id,
+            SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
 
-        // Increment enough blocks such that now == expiry
-        System::set_block_number(ProposalLifetime::get() + 1);
-
-        // Attempt to submit a vote should fail
-        assert_noop!(
-            Bridge::reject_proposal(
-                Origin::signed(RELAYER_B),
-                prop_id,
-                src_id,
-                r_id,
-                Box::new(proposal.clone())
-            ),
-            Error::<Test>::ProposalExpired
-        );
+        for relayer in &[RELAYER_A, RELAYER_B, RELAYER_C] {
+            assert_ok!(Bridge::remove_relayer(Origin::root(), *relayer));
+        }
+        assert_eq!(Bridge::relayer_count(), 0);
 
-        // Proposal state should remain unchanged
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            SrcChainId(src_id),
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, proposal.clone())).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Initiated);
 
-        // eval_vote_state should have no effect
+        System::set_block_number(prop.expiry);
         assert_noop!(
             Bridge::eval_vote_state(
-                Origin::signed(RELAYER_C),
+                Origin::signed(RELAYER_A),
                 prop_id,
-                src_id,
+                SrcChainId(src_id),
                 Box::new(proposal.clone())
             ),
             Error::<Test>::ProposalExpired
         );
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
-
-        assert_events(vec![Event::bridge(RawEvent::VoteFor(
-            src_id, prop_id, RELAYER_A,
-        ))]);
+        assert_eq!(Bridge::proposal_outcome(src_id, prop_id), None);
     })
 }
@@ -0,0 +1,31 @@
+//! Weights for the chainbridge pallet
+
+use frame_support::weights::Weight;
+
+/// Weight functions needed by the chainbridge pallet.
+pub trait WeightInfo {
+    /// Weight of `acknowledge_proposal`, excluding the proposed call, where `c` is the encoded
+    /// length of the call
+    fn acknowledge_proposal(c: u32) -> Weight;
+    /// Weight of `expire_proposal` for a proposal with a vote and proof from every relayer
+    fn expire_proposal() -> Weight;
+    /// Weight of `import_state` for a snapshot with `r` relayers, `c` chains and `s` resources
+    fn import_state(r: u32, c: u32, s: u32) -> Weight;
+}
+
+// Keeps the previous flat weights, plus an estimated cost per byte of the stored call or per
+// imported entry. Runtimes should provide their own values from `benchmarking.rs`.
+impl WeightInfo for () {
+    fn acknowledge_proposal(c: u32) -> Weight {
+        195_000_000u64.saturating_add(2_000u64.saturating_mul(c.into()))
+    }
+
+    fn expire_proposal() -> Weight {
+        195_000_000
+    }
+
+    fn import_state(r: u32, c: u32, s: u32) -> Weight {
+        let entries = r.saturating_add(c).saturating_add(s);
+        195_000_000u64.saturating_add(25_000_000u64.saturating_mul(entries.into()))
+    }
+}
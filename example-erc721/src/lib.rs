@@ -12,6 +12,7 @@ use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
 mod mock;
+pub mod runtime_api;
 mod tests;
 
 type TokenId = U256;
@@ -28,6 +29,12 @@ pub trait Config: system::Config {
     /// Some identifier for this token type, possibly the originating ethereum address.
     /// This is not explicitly used for anything, but may reflect the bridge's notion of resource ID.
     type Identifier: Get<[u8; 32]>;
+
+    /// Maximum length of a token's metadata
+    type MaxMetadataLen: Get<u32>;
+
+    /// Maximum number of tokens minted in a single batch
+    type MaxBatchSize: Get<u32>;
 }
 
 decl_event! {
@@ -52,6 +59,10 @@ decl_error! {
         TokenAlreadyExists,
         /// Origin is not owner
         NotOwner,
+        /// Metadata exceeds the maximum allowed length
+        MetadataTooLong,
+        /// Batch holds more than `MaxBatchSize` tokens
+        BatchTooLarge,
     }
 }
 
@@ -69,6 +80,9 @@ decl_storage! {
 decl_module! {
     pub struct Module<T: Config> for enum Call where origin: T::Origin {
         type Error = Error<T>;
+        const MaxMetadataLen: u32 = T::MaxMetadataLen::get();
+        const MaxBatchSize: u32 = T::MaxBatchSize::get();
+
         fn deposit_event() = default;
 
         /// Creates a new token with the given token ID and metadata, and gives ownership to owner
@@ -81,6 +95,27 @@ decl_module! {
             Ok(())
         }
 
+        /// Creates several tokens owned by owner. No tokens are created if any ID already
+        /// exists or any metadata is too long.
+        #[weight = 195_000_000]
+        pub fn mint_batch(origin, owner: T::AccountId, tokens: Vec<(TokenId, Vec<u8>)>) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(tokens.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+
+            for (i, (id, metadata)) in tokens.iter().enumerate() {
+                ensure!(
+                    !Tokens::contains_key(id) && !tokens[..i].iter().any(|(prev, _)| prev == id),
+                    Error::<T>::TokenAlreadyExists
+                );
+                ensure!(metadata.len() <= T::MaxMetadataLen::get() as usize, Error::<T>::MetadataTooLong);
+            }
+            for (id, metadata) in tokens {
+                Self::mint_token(owner.clone(), id, metadata)?;
+            }
+
+            Ok(())
+        }
+
         /// Changes ownership of a token sender owns
         #[weight = 195_000_000]
         pub fn transfer(origin, to: T::AccountId, id: TokenId) -> DispatchResult {
@@ -106,9 +141,18 @@ decl_module! {
 }
 
 impl<T: Config> Module<T> {
+    /// Checks if a token with the given ID has been minted and not burned
+    pub fn token_exists(id: TokenId) -> bool {
+        <Tokens>::contains_key(id)
+    }
+
     /// Creates a new token in the system.
     pub fn mint_token(owner: T::AccountId, id: TokenId, metadata: Vec<u8>) -> DispatchResult {
         ensure!(!Tokens::contains_key(id), Error::<T>::TokenAlreadyExists);
+        ensure!(
+            metadata.len() <= T::MaxMetadataLen::get() as usize,
+            Error::<T>::MetadataTooLong
+        );
 
         let new_token = Erc721Token { id, metadata };
 
@@ -67,11 +67,15 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
     pub Erc721Id: bridge::ResourceId = bridge::derive_resource_id(1, &blake2_128(b"NFT"));
+    pub const MaxMetadataLen: u32 = 32;
+    pub const MaxBatchSize: u32 = 4;
 }
 
 impl Config for Test {
     type Event = Event;
     type Identifier = Erc721Id;
+    type MaxMetadataLen = MaxMetadataLen;
+    type MaxBatchSize = MaxBatchSize;
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -0,0 +1,20 @@
+//! Runtime API definition for the ERC721 pallet.
+
+// The generated client-side code takes more arguments than clippy allows, and repeats the
+// bounds on the API's type parameters.
+#![allow(clippy::too_many_arguments, clippy::multiple_bound_locations)]
+
+use codec::Codec;
+use sp_core::U256;
+
+sp_api::decl_runtime_apis! {
+    /// Exposes token ownership so clients don't need to read storage directly.
+    pub trait Erc721Api<AccountId> where AccountId: Codec {
+        /// Owner of the token, if it exists. See `Module::owner_of`.
+        fn owner_of(id: U256) -> Option<AccountId>;
+        /// Whether the token exists. See `Module::token_exists`.
+        fn token_exists(id: U256) -> bool;
+        /// Total number of tokens in existence. See `Module::token_count`.
+        fn token_count() -> U256;
+    }
+}
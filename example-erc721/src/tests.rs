@@ -1,8 +1,11 @@
 #![cfg(test)]
 
-use super::mock::{new_test_ext, Erc721, Origin, Test, USER_A, USER_B, USER_C};
+use super::mock::{
+    new_test_ext, Erc721, Event, MaxBatchSize, MaxMetadataLen, Origin, System, Test, USER_A,
+    USER_B, USER_C,
+};
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
 use sp_core::U256;
 
 #[test]
@@ -97,3 +100,97 @@ fn transfer_tokens() {
         assert_eq!(Erc721::owner_of(id_b).unwrap(), USER_A);
     })
 }
+
+#[test]
+fn mint_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let tokens: Vec<(U256, Vec<u8>)> = vec![
+            (1.into(), vec![1]),
+            (2.into(), vec![2]),
+            (3.into(), vec![3]),
+        ];
+
+        assert_ok!(Erc721::mint_batch(Origin::root(), USER_A, tokens.clone()));
+        assert_eq!(Erc721::token_count(), 3.into());
+        for (id, metadata) in tokens.clone() {
+            assert_eq!(Erc721::owner_of(id), Some(USER_A));
+            assert_eq!(Erc721::tokens(id).unwrap(), Erc721Token { id, metadata });
+        }
+        let minted: Vec<Event> = tokens
+            .iter()
+            .map(|(id, _)| Event::erc721(RawEvent::Minted(USER_A, *id)))
+            .collect();
+        let events: Vec<Event> = System::events().into_iter().map(|e| e.event).collect();
+        assert_eq!(events, minted);
+    })
+}
+
+#[test]
+fn mint_batch_is_atomic() {
+    new_test_ext().execute_with(|| {
+        let id_a: U256 = 1.into();
+        let id_b: U256 = 2.into();
+        let id_c: U256 = 3.into();
+        assert_ok!(Erc721::mint(Origin::root(), USER_A, id_a, vec![]));
+
+        assert_noop!(
+            Erc721::mint_batch(Origin::signed(USER_A), USER_A, vec![(id_b, vec![])]),
+            DispatchError::BadOrigin
+        );
+        // Existing token
+        assert_noop!(
+            Erc721::mint_batch(
+                Origin::root(),
+                USER_B,
+                vec![(id_b, vec![]), (id_a, vec![]), (id_c, vec![])]
+            ),
+            Error::<Test>::TokenAlreadyExists
+        );
+        // Duplicate within the batch
+        assert_noop!(
+            Erc721::mint_batch(Origin::root(), USER_B, vec![(id_b, vec![]), (id_b, vec![])]),
+            Error::<Test>::TokenAlreadyExists
+        );
+        assert_noop!(
+            Erc721::mint_batch(
+                Origin::root(),
+                USER_B,
+                vec![
+                    (id_b, vec![]),
+                    (id_c, vec![0; MaxMetadataLen::get() as usize + 1])
+                ]
+            ),
+            Error::<Test>::MetadataTooLong
+        );
+        let too_many = (2..=MaxBatchSize::get() as u64 + 2)
+            .map(|id| (id.into(), vec![]))
+            .collect();
+        assert_noop!(
+            Erc721::mint_batch(Origin::root(), USER_B, too_many),
+            Error::<Test>::BatchTooLarge
+        );
+        assert_eq!(Erc721::token_count(), 1.into());
+    })
+}
+
+#[test]
+fn runtime_api_queries() {
+    new_test_ext().execute_with(|| {
+        let id_a: U256 = 1.into();
+        let id_b: U256 = 2.into();
+
+        assert_eq!(Erc721::owner_of(id_a), None);
+        assert!(!Erc721::token_exists(id_a));
+        assert_eq!(Erc721::token_count(), 0.into());
+
+        assert_ok!(Erc721::mint(Origin::root(), USER_A, id_a, vec![]));
+        assert_ok!(Erc721::mint(Origin::root(), USER_B, id_b, vec![]));
+
+        assert_eq!(Erc721::owner_of(id_a), Some(USER_A));
+        assert_eq!(Erc721::owner_of(id_b), Some(USER_B));
+        assert!(Erc721::token_exists(id_a));
+        assert!(!Erc721::token_exists(3.into()));
+        assert_eq!(Erc721::token_count(), 2.into());
+    })
+}
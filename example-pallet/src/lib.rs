@@ -2,13 +2,27 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use chainbridge as bridge;
+use codec::DecodeAll;
 use example_erc721 as erc721;
-use frame_support::traits::{Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get};
-use frame_support::{decl_error, decl_event, decl_module, dispatch::DispatchResult, ensure};
+use frame_support::traits::{
+    Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get, ReservableCurrency,
+};
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult},
+    ensure, transactional,
+};
 use frame_system::{self as system, ensure_signed};
-use sp_arithmetic::traits::SaturatedConversion;
 use sp_core::U256;
-use sp_std::prelude::*;
+use sp_runtime::{
+    traits::{CheckedSub, Zero},
+    RuntimeDebug,
+};
+use sp_std::{
+    convert::{TryFrom, TryInto},
+    marker::PhantomData,
+    prelude::*,
+};
 
 mod mock;
 mod tests;
@@ -18,31 +32,189 @@ type ResourceId = bridge::ResourceId;
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// Builds the call to be proposed for an inbound transfer from its resource ID and payload.
+pub trait CallRouter<Call> {
+    /// Returns `None` if the resource ID is unknown or the payload is malformed.
+    fn route(resource_id: ResourceId, payload: &[u8]) -> Option<Call>;
+}
+
+/// Routes the resources handled by this pallet to its executable calls.
+///
+/// Payloads are the SCALE encoded arguments of the call, excluding the resource ID:
+/// - `HashId`: `hash`
+/// - `NativeTokenId`: `(to, amount)`, where `amount` is a `U256` converted with `AmountConverter`
+/// - `Erc721Id`: `(recipient, id, metadata)`
+pub struct DefaultRouter<T>(PhantomData<T>);
+impl<T: Config> CallRouter<<T as bridge::Config>::Proposal> for DefaultRouter<T>
+where
+    <T as bridge::Config>::Proposal: From<Call<T>>,
+{
+    fn route(resource_id: ResourceId, payload: &[u8]) -> Option<<T as bridge::Config>::Proposal> {
+        let call = if resource_id == T::HashId::get() {
+            let hash = T::Hash::decode_all(payload).ok()?;
+            Call::<T>::remark(hash, resource_id)
+        } else if resource_id == T::NativeTokenId::get() {
+            let (to, amount) = <(T::AccountId, U256)>::decode_all(payload).ok()?;
+            Call::<T>::transfer(to, T::AmountConverter::from_u256(amount)?, resource_id)
+        } else if resource_id == T::Erc721Id::get() {
+            let (recipient, id, metadata) =
+                <(T::AccountId, U256, Vec<u8>)>::decode_all(payload).ok()?;
+            Call::<T>::mint_erc721(recipient, id, metadata, resource_id)
+        } else {
+            return None;
+        };
+        Some(call.into())
+    }
+}
+
+/// Converts between native balances and the `U256` amounts used by other chains.
+pub trait AmountConverter<Balance> {
+    /// Returns `None` if the balance can't be represented as a `U256`.
+    fn to_u256(balance: Balance) -> Option<U256>;
+    /// Returns `None` if the amount doesn't fit in a `Balance`.
+    fn from_u256(amount: U256) -> Option<Balance>;
+}
+
+/// Converts amounts of up to `u128::MAX` that also fit in the balance type.
+pub struct CheckedAmount;
+impl<B: TryFrom<u128> + TryInto<u128>> AmountConverter<B> for CheckedAmount {
+    fn to_u256(balance: B) -> Option<U256> {
+        balance.try_into().ok().map(U256::from)
+    }
+
+    fn from_u256(amount: U256) -> Option<B> {
+        if amount > U256::from(u128::MAX) {
+            return None;
+        }
+        B::try_from(amount.as_u128()).ok()
+    }
+}
+
+/// Checks that a recipient is correctly encoded for the destination chain.
+pub trait RecipientCodec {
+    fn validate(recipient: &[u8]) -> bool;
+}
+
+/// Accepts 20 byte recipients, as used by EVM chains.
+pub struct EvmAddress;
+impl RecipientCodec for EvmAddress {
+    fn validate(recipient: &[u8]) -> bool {
+        recipient.len() == 20
+    }
+}
+
+/// Where native tokens are held while bridged to another chain.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CustodyMode {
+    /// Tokens are transferred to the bridge account, and paid out of it by inbound transfers.
+    BridgeAccount,
+    /// Tokens are reserved on the sender's own account, so they stay with the user but can't be
+    /// spent, and are never mixed with the bridge account's funds. Inbound transfers unreserve
+    /// the recipient's bridged funds, so they can only return tokens to an account that sent at
+    /// least as much out.
+    Reserve,
+}
+
 pub trait Config: system::Config + bridge::Config + erc721::Config {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
     /// Specifies the origin check provided by the bridge for calls that can only be called by the bridge pallet
     type BridgeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 
     /// The currency mechanism.
-    type Currency: Currency<Self::AccountId>;
+    type Currency: ReservableCurrency<Self::AccountId>;
 
     /// Ids can be defined by the runtime and passed in, perhaps from blake2b_128 hashes.
     type HashId: Get<ResourceId>;
     type NativeTokenId: Get<ResourceId>;
     type Erc721Id: Get<ResourceId>;
+
+    /// Maps inbound resource IDs and payloads to the calls that should be proposed.
+    type CallRouter: CallRouter<<Self as bridge::Config>::Proposal>;
+
+    /// Number of blocks after which an unconfirmed native transfer may be cancelled by its sender.
+    type CancelDelay: Get<Self::BlockNumber>;
+
+    /// Maximum number of unconfirmed native transfers each sender may have at once.
+    type MaxPendingNativeTransfers: Get<u32>;
+
+    /// Expected encoding of recipients on destination chains.
+    type RecipientCodec: RecipientCodec;
+
+    /// Whether native tokens can be locked and bridged with `transfer_native`. Runtimes that
+    /// only bridge hashes and ERC721 tokens should set this to `false`.
+    type EnableNativeTransfer: Get<bool>;
+
+    /// How native tokens are held while bridged.
+    type CustodyMode: Get<CustodyMode>;
+
+    /// Flat fee charged to the sender of each `transfer_native`, paid into the bridge's fee
+    /// account on top of the bridged amount. Fees aren't refunded if the transfer is cancelled.
+    type NativeTransferFee: Get<BalanceOf<Self>>;
+
+    /// Conversion of native amounts to and from `U256`, failing rather than truncating.
+    type AmountConverter: AmountConverter<BalanceOf<Self>>;
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as Example {
+        /// Native transfers awaiting confirmation from the destination chain, keyed by
+        /// destination chain and deposit nonce (sender, amount, block initiated)
+        pub PendingNativeTransfers get(fn pending_native_transfers):
+            double_map hasher(opaque_blake2_256) bridge::ChainId, hasher(opaque_blake2_256) bridge::DepositNonce
+            => Option<(T::AccountId, BalanceOf<T>, T::BlockNumber)>;
+
+        /// Number of entries in `PendingNativeTransfers` for each sender
+        pub PendingNativeTransferCount get(fn pending_native_transfer_count):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// Amount each account has reserved for bridged transfers with `CustodyMode::Reserve`,
+        /// kept apart from reserves made by other pallets
+        pub BridgedReserves get(fn bridged_reserve):
+            map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+    }
 }
 
 decl_event! {
     pub enum Event<T> where
         <T as frame_system::Config>::Hash,
+        <T as frame_system::Config>::AccountId,
+        Balance = BalanceOf<T>,
     {
         Remark(Hash),
+        /// Native tokens were released from custody by an inbound transfer (to, amount, resource_id)
+        FungibleReleased(AccountId, Balance, ResourceId),
+        /// Native transfer has been received by the destination chain (dest_id, nonce)
+        NativeTransferConfirmed(bridge::ChainId, bridge::DepositNonce),
+        /// Native transfer was cancelled and refunded to the sender (dest_id, nonce, sender)
+        NativeTransferCancelled(bridge::ChainId, bridge::DepositNonce, AccountId),
+        /// Collected fees were transferred out of the bridge's fee account (to, amount)
+        FeesWithdrawn(AccountId, Balance),
     }
 }
 
 decl_error! {
     pub enum Error for Module<T: Config>{
         InvalidTransfer,
+        /// No unconfirmed native transfer exists with the given chain and nonce
+        TransferNotPending,
+        /// Only the sender of a native transfer may cancel it
+        NotTransferSender,
+        /// The transfer cannot be cancelled until `CancelDelay` blocks have passed
+        CancelTooEarly,
+        /// Sender already has `MaxPendingNativeTransfers` unconfirmed native transfers
+        TooManyPendingTransfers,
+        /// Recipient is not correctly encoded for the destination chain
+        InvalidRecipient,
+        /// Resource ID isn't registered with the bridge
+        UnknownResource,
+        /// Native token transfers are disabled in this runtime
+        NativeTransferDisabled,
+        /// Recipient doesn't have enough bridged funds reserved to pay out
+        InsufficientReserved,
+        /// Amount can't be converted without overflowing
+        AmountOverflow,
+        /// Fee account doesn't hold enough to cover the withdrawal
+        InsufficientFees,
     }
 }
 
@@ -51,6 +223,9 @@ decl_module! {
         const HashId: ResourceId = T::HashId::get();
         const NativeTokenId: ResourceId = T::NativeTokenId::get();
         const Erc721Id: ResourceId = T::Erc721Id::get();
+        const CancelDelay: T::BlockNumber = T::CancelDelay::get();
+        const MaxPendingNativeTransfers: u32 = T::MaxPendingNativeTransfers::get();
+        const NativeTransferFee: BalanceOf<T> = T::NativeTransferFee::get();
 
         fn deposit_event() = default;
 
@@ -61,37 +236,86 @@ decl_module! {
         /// Transfers an arbitrary hash to a (whitelisted) destination chain.
         #[weight = 195_000_000]
         pub fn transfer_hash(origin, hash: T::Hash, dest_id: bridge::ChainId) -> DispatchResult {
-            ensure_signed(origin)?;
+            let source = ensure_signed(origin)?;
 
             let resource_id = T::HashId::get();
             let metadata: Vec<u8> = hash.as_ref().to_vec();
-            <bridge::Module<T>>::transfer_generic(dest_id, resource_id, metadata)
+            <bridge::Module<T>>::transfer_generic(dest_id, resource_id, metadata, Some(source))
+        }
+
+        /// Transfers arbitrary data to a (whitelisted) destination chain under a resource chosen by the caller.
+        #[weight = 195_000_000]
+        pub fn transfer_generic_with_resource(origin, resource_id: ResourceId, metadata: Vec<u8>, dest_id: bridge::ChainId) -> DispatchResult {
+            let source = ensure_signed(origin)?;
+            ensure!(<bridge::Module<T>>::resource_exists(resource_id), Error::<T>::UnknownResource);
+
+            <bridge::Module<T>>::transfer_generic(dest_id, resource_id, metadata, Some(source))
         }
 
         /// Transfers some amount of the native token to some recipient on a (whitelisted) destination chain.
+        /// Nothing is moved if the bridge rejects the transfer.
         #[weight = 195_000_000]
+        #[transactional]
         pub fn transfer_native(origin, amount: BalanceOf<T>, recipient: Vec<u8>, dest_id: bridge::ChainId) -> DispatchResult {
             let source = ensure_signed(origin)?;
+            ensure!(T::EnableNativeTransfer::get(), Error::<T>::NativeTransferDisabled);
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
-            let bridge_id = <bridge::Module<T>>::account_id();
-            T::Currency::transfer(&source, &bridge_id, amount.into(), AllowDeath)?;
+            ensure!(T::RecipientCodec::validate(&recipient), Error::<T>::InvalidRecipient);
+            ensure!(
+                Self::pending_native_transfer_count(&source) < T::MaxPendingNativeTransfers::get(),
+                Error::<T>::TooManyPendingTransfers
+            );
+            let bridged_amount = T::AmountConverter::to_u256(amount).ok_or(Error::<T>::AmountOverflow)?;
+            let fee = T::NativeTransferFee::get();
+            if !fee.is_zero() {
+                let fee_account = <bridge::Module<T>>::fee_account_id();
+                T::Currency::transfer(&source, &fee_account, fee, AllowDeath)?;
+            }
+            Self::lock_funds(&source, amount)?;
 
             let resource_id = T::NativeTokenId::get();
-            <bridge::Module<T>>::transfer_fungible(dest_id, resource_id, recipient, U256::from(amount.saturated_into::<u128>()))
+            <bridge::Module<T>>::transfer_fungible(dest_id, resource_id, recipient, bridged_amount, vec![], Some(source.clone()))?;
+
+            let nonce = <bridge::Module<T>>::chains(dest_id).unwrap_or_default();
+            let now = <frame_system::Module<T>>::block_number();
+            <PendingNativeTransferCount<T>>::mutate(&source, |count| *count += 1);
+            <PendingNativeTransfers<T>>::insert(dest_id, nonce, (source, amount, now));
+            Ok(())
+        }
+
+        /// Refunds a native transfer that hasn't been confirmed by the destination chain
+        /// within `CancelDelay` blocks. Can only be called by the original sender.
+        #[weight = 195_000_000]
+        #[transactional]
+        pub fn cancel_native_transfer(origin, dest_id: bridge::ChainId, nonce: bridge::DepositNonce) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (sender, _, initiated) = Self::pending_native_transfers(dest_id, nonce)
+                .ok_or(Error::<T>::TransferNotPending)?;
+            ensure!(who == sender, Error::<T>::NotTransferSender);
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now >= initiated + T::CancelDelay::get(), Error::<T>::CancelTooEarly);
+
+            let (sender, amount) = Self::take_pending_transfer(dest_id, nonce)?;
+            Self::release_funds(&sender, amount)?;
+            Self::deposit_event(RawEvent::NativeTransferCancelled(dest_id, nonce, sender));
+            Ok(())
         }
 
         /// Transfer a non-fungible token (erc721) to a (whitelisted) destination chain.
+        /// The token isn't burned if the bridge rejects the transfer.
         #[weight = 195_000_000]
+        #[transactional]
         pub fn transfer_erc721(origin, recipient: Vec<u8>, token_id: U256, dest_id: bridge::ChainId) -> DispatchResult {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
+            ensure!(T::RecipientCodec::validate(&recipient), Error::<T>::InvalidRecipient);
             match <erc721::Module<T>>::tokens(&token_id) {
                 Some(token) => {
-                    <erc721::Module<T>>::burn_token(source, token_id)?;
+                    <erc721::Module<T>>::burn_token(source.clone(), token_id)?;
                     let resource_id = T::Erc721Id::get();
                     let tid: &mut [u8] = &mut[0; 32];
                     token_id.to_big_endian(tid);
-                    <bridge::Module<T>>::transfer_nonfungible(dest_id, resource_id, tid.to_vec(), recipient, token.metadata)
+                    <bridge::Module<T>>::transfer_nonfungible(dest_id, resource_id, tid.to_vec(), recipient, token.metadata, Some(source))
                 }
                 None => Err(Error::<T>::InvalidTransfer)?
             }
@@ -101,11 +325,15 @@ decl_module! {
         // Executable calls. These can be triggered by a bridge transfer initiated on another chain
         //
 
-        /// Executes a simple currency transfer using the bridge account as the source
+        /// Executes a simple currency transfer using the bridge account as the source. With
+        /// `CustodyMode::Reserve` the amount is unreserved from the recipient's bridged funds
+        /// instead.
         #[weight = 195_000_000]
+        #[transactional]
         pub fn transfer(origin, to: T::AccountId, amount: BalanceOf<T>, r_id: ResourceId) -> DispatchResult {
-            let source = T::BridgeOrigin::ensure_origin(origin)?;
-            <T as Config>::Currency::transfer(&source, &to, amount.into(), AllowDeath)?;
+            T::BridgeOrigin::ensure_origin(origin)?;
+            Self::release_funds(&to, amount)?;
+            Self::deposit_event(RawEvent::FungibleReleased(to, amount, r_id));
             Ok(())
         }
 
@@ -117,6 +345,15 @@ decl_module! {
             Ok(())
         }
 
+        /// Marks a native transfer as received by the destination chain, so it can no longer be cancelled
+        #[weight = 195_000_000]
+        pub fn confirm_native_transfer(origin, dest_id: bridge::ChainId, nonce: bridge::DepositNonce, r_id: ResourceId) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            Self::take_pending_transfer(dest_id, nonce)?;
+            Self::deposit_event(RawEvent::NativeTransferConfirmed(dest_id, nonce));
+            Ok(())
+        }
+
         /// Allows the bridge to issue new erc721 tokens
         #[weight = 195_000_000]
         pub fn mint_erc721(origin, recipient: T::AccountId, id: U256, metadata: Vec<u8>, r_id: ResourceId) -> DispatchResult {
@@ -124,5 +361,83 @@ decl_module! {
             <erc721::Module<T>>::mint_token(recipient, id, metadata)?;
             Ok(())
         }
+
+        //
+        // Admin calls
+        //
+
+        /// Transfers fees collected in the bridge's fee account to `to`
+        #[weight = 195_000_000]
+        pub fn withdraw_fees(origin, to: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            <bridge::Module<T>>::ensure_admin(origin)?;
+            let fee_account = <bridge::Module<T>>::fee_account_id();
+            ensure!(
+                <T as Config>::Currency::free_balance(&fee_account) >= amount,
+                Error::<T>::InsufficientFees
+            );
+            <T as Config>::Currency::transfer(&fee_account, &to, amount, AllowDeath)?;
+            Self::deposit_event(RawEvent::FeesWithdrawn(to, amount));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Takes `amount` of `who`'s funds into custody for an outbound transfer
+    fn lock_funds(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+        match T::CustodyMode::get() {
+            CustodyMode::BridgeAccount => {
+                let bridge_id = <bridge::Module<T>>::account_id();
+                <T as Config>::Currency::transfer(who, &bridge_id, amount, AllowDeath)
+            }
+            CustodyMode::Reserve => {
+                <T as Config>::Currency::reserve(who, amount)?;
+                <BridgedReserves<T>>::mutate(who, |reserved| *reserved += amount);
+                Ok(())
+            }
+        }
+    }
+
+    /// Releases `amount` from custody to `to`. With `CustodyMode::Reserve` only funds `to`
+    /// reserved for bridged transfers are released, never reserves made by other pallets.
+    fn release_funds(to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+        match T::CustodyMode::get() {
+            CustodyMode::BridgeAccount => {
+                let bridge_id = <bridge::Module<T>>::account_id();
+                <T as Config>::Currency::transfer(&bridge_id, to, amount, AllowDeath)
+            }
+            CustodyMode::Reserve => <BridgedReserves<T>>::try_mutate_exists(to, |reserved| {
+                let remaining = reserved
+                    .unwrap_or_default()
+                    .checked_sub(&amount)
+                    .ok_or(Error::<T>::InsufficientReserved)?;
+                let missing = <T as Config>::Currency::unreserve(to, amount);
+                ensure!(missing.is_zero(), Error::<T>::InsufficientReserved);
+                *reserved = Some(remaining).filter(|r| !r.is_zero());
+                Ok(())
+            }),
+        }
+    }
+
+    /// Removes an unconfirmed native transfer, returning its sender and amount
+    fn take_pending_transfer(
+        dest_id: bridge::ChainId,
+        nonce: bridge::DepositNonce,
+    ) -> Result<(T::AccountId, BalanceOf<T>), DispatchError> {
+        let (sender, amount, _) = <PendingNativeTransfers<T>>::take(dest_id, nonce)
+            .ok_or(Error::<T>::TransferNotPending)?;
+        <PendingNativeTransferCount<T>>::mutate_exists(&sender, |count| {
+            *count = count.map(|n| n.saturating_sub(1)).filter(|n| *n > 0);
+        });
+        Ok((sender, amount))
+    }
+
+    /// Builds the proposal for an inbound transfer of `resource_id` with the given payload.
+    /// Relayers can use this to construct the same call for a transfer independently.
+    pub fn route_call(
+        resource_id: ResourceId,
+        payload: &[u8],
+    ) -> Option<<T as bridge::Config>::Proposal> {
+        T::CallRouter::route(resource_id, payload)
     }
 }
@@ -70,25 +70,103 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const TestChainId: u8 = 5;
     pub const ProposalLifetime: u64 = 100;
+    pub const MaxRelayers: u32 = 3;
+    pub const MaxFungibleMetadataLen: u32 = 32;
+    pub const MaxFungibleRecipients: u32 = 3;
+    pub const MaxBatchSize: u32 = 4;
+    pub const MaxProposalNoteLen: u32 = 32;
+    pub const MaxVoteProofLen: u32 = 64;
+    pub const MaxChainNameLen: u32 = 16;
+    pub const MaxRecentTransfers: u32 = 3;
+    pub const MaxTotalActiveProposals: u32 = 100;
+    pub const TransferVolumeAlertThreshold: u32 = 3;
+    pub const NonceStep: u64 = 1;
+    pub const MinConfirmations: u64 = 0;
+    pub const ReportDuplicateVotes: bool = false;
+    pub const RequireVoteProof: bool = false;
+    pub const RejectStaleNonces: bool = false;
+    pub const RejectRemovedResources: bool = false;
+    pub const AllowRejectCreatesProposal: bool = true;
+    pub const MethodMismatch: bridge::MethodMismatchMode = bridge::MethodMismatchMode::Reject;
+    pub const WhitelistChangeCooldown: u64 = 0;
+    pub const VerboseOnboarding: bool = false;
+    pub const VerboseVoteEvents: bool = false;
+    pub const AutoWhitelistOnRelayerAssign: bool = false;
+    pub const TieBreak: bridge::TieBreakMode = bridge::TieBreakMode::ApproveWins;
+    pub const StakeThreshold: Option<Perbill> = None;
+    pub const RelayerActivationDelay: u64 = 0;
 }
 
 impl bridge::Config for Test {
     type Event = Event;
     type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type VetoOrigin = frame_system::EnsureSignedBy<One, Self::AccountId>;
+    type TransferOrigin = frame_system::EnsureSignedBy<One, Self::AccountId>;
     type Proposal = Call;
     type ChainId = TestChainId;
     type ProposalLifetime = ProposalLifetime;
+    type MaxRelayers = MaxRelayers;
+    type MaxFungibleMetadataLen = MaxFungibleMetadataLen;
+    type MaxFungibleRecipients = MaxFungibleRecipients;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxTokenIdLen = MaxTokenIdLen;
+    type MaxNftMetadataLen = MaxNftMetadataLen;
+    type MaxProposalNoteLen = MaxProposalNoteLen;
+    type MaxVoteProofLen = MaxVoteProofLen;
+    type MaxChainNameLen = MaxChainNameLen;
+    type MaxRecentTransfers = MaxRecentTransfers;
+    type MaxTotalActiveProposals = MaxTotalActiveProposals;
+    type NonceStep = NonceStep;
+    type TransferVolumeAlertThreshold = TransferVolumeAlertThreshold;
+    type MinConfirmations = MinConfirmations;
+    type ReportDuplicateVotes = ReportDuplicateVotes;
+    type InboundRateLimiter = ();
+    type RequireVoteProof = RequireVoteProof;
+    type RejectStaleNonces = RejectStaleNonces;
+    type RejectRemovedResources = RejectRemovedResources;
+    type AllowRejectCreatesProposal = AllowRejectCreatesProposal;
+    type MethodMismatch = MethodMismatch;
+    type WhitelistChangeCooldown = WhitelistChangeCooldown;
+    type VerboseOnboarding = VerboseOnboarding;
+    type VerboseVoteEvents = VerboseVoteEvents;
+    type AutoWhitelistOnRelayerAssign = AutoWhitelistOnRelayerAssign;
+    type ProposalFilter = ();
+    type TieBreak = TieBreak;
+    type StakeProvider = bridge::EqualStake<Test>;
+    type StakeThreshold = StakeThreshold;
+    type RelayerActivationDelay = RelayerActivationDelay;
+    type OnProposalApproved = ();
+    type OnProposalResolved = ();
+    type MaxBridgeDepth = MaxBridgeDepth;
+    type OnChainWhitelisted = ();
+    type OnChainRemoved = ();
+    type WeightInfo = ();
+    type ResourceIdScheme = bridge::EvmResourceId;
 }
 
 parameter_types! {
     pub HashId: bridge::ResourceId = bridge::derive_resource_id(1, &blake2_128(b"hash"));
     pub NativeTokenId: bridge::ResourceId = bridge::derive_resource_id(1, &blake2_128(b"DAV"));
     pub Erc721Id: bridge::ResourceId = bridge::derive_resource_id(1, &blake2_128(b"NFT"));
+    pub const CancelDelay: u64 = 10;
+    pub const MaxPendingNativeTransfers: u32 = 2;
+    pub const MaxMetadataLen: u32 = 32;
+}
+
+parameter_types! {
+    pub static EnableNativeTransfer: bool = true;
+    pub static MaxBridgeDepth: u8 = 1;
+    pub static MaxTokenIdLen: u32 = 32;
+    pub static MaxNftMetadataLen: u32 = 64;
+    pub static Custody: CustodyMode = CustodyMode::BridgeAccount;
+    pub static NativeTransferFee: u64 = 0;
 }
 
 impl erc721::Config for Test {
     type Event = Event;
     type Identifier = Erc721Id;
+    type MaxMetadataLen = MaxMetadataLen;
+    type MaxBatchSize = MaxBatchSize;
 }
 
 impl Config for Test {
@@ -98,6 +176,14 @@ impl Config for Test {
     type HashId = HashId;
     type NativeTokenId = NativeTokenId;
     type Erc721Id = Erc721Id;
+    type CallRouter = DefaultRouter<Test>;
+    type CancelDelay = CancelDelay;
+    type MaxPendingNativeTransfers = MaxPendingNativeTransfers;
+    type RecipientCodec = EvmAddress;
+    type EnableNativeTransfer = EnableNativeTransfer;
+    type CustodyMode = Custody;
+    type NativeTransferFee = NativeTransferFee;
+    type AmountConverter = CheckedAmount;
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -113,7 +199,7 @@ frame_support::construct_runtime!(
         Balances: balances::{Module, Call, Storage, Config<T>, Event<T>},
         Bridge: bridge::{Module, Call, Storage, Event<T>},
         Erc721: erc721::{Module, Call, Storage, Event<T>},
-        Example: example::{Module, Call, Event<T>}
+        Example: example::{Module, Call, Storage, Event<T>}
     }
 );
 
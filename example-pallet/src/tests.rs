@@ -2,16 +2,20 @@
 
 use super::mock::{
     assert_events, balances, event_exists, expect_event, new_test_ext, Balances, Bridge, Call,
-    Erc721, Erc721Id, Event, Example, HashId, NativeTokenId, Origin, ProposalLifetime, Test,
-    ENDOWED_BALANCE, RELAYER_A, RELAYER_B, RELAYER_C,
+    CancelDelay, Custody, EnableNativeTransfer, Erc721, Erc721Id, Event, Example, HashId,
+    MaxBridgeDepth, MaxNftMetadataLen, MaxPendingNativeTransfers, MaxTokenIdLen, NativeTokenId,
+    NativeTransferFee, Origin, ProposalLifetime, System, Test, ENDOWED_BALANCE, RELAYER_A,
+    RELAYER_B, RELAYER_C,
 };
 use super::*;
 use frame_support::dispatch::DispatchError;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::weights::GetDispatchInfo;
+use frame_support::{assert_err, assert_noop, assert_ok};
 
 use codec::Encode;
 use example_erc721::Erc721Token;
 use sp_core::{blake2_256, H256};
+use sp_std::convert::TryInto;
 
 const TEST_THRESHOLD: u32 = 2;
 
@@ -46,6 +50,47 @@ fn transfer_hash() {
             1,
             resource_id,
             hash.as_ref().to_vec(),
+            Some(1),
+        ));
+    })
+}
+
+#[test]
+fn transfer_generic_with_resource() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let resource_id = bridge::derive_resource_id(dest_chain, b"data");
+        let metadata = vec![1, 2, 3];
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_noop!(
+            Example::transfer_generic_with_resource(
+                Origin::signed(1),
+                resource_id,
+                metadata.clone(),
+                dest_chain
+            ),
+            Error::<Test>::UnknownResource
+        );
+
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            resource_id,
+            b"Example.remark".to_vec()
+        ));
+        assert_ok!(Example::transfer_generic_with_resource(
+            Origin::signed(1),
+            resource_id,
+            metadata.clone(),
+            dest_chain
+        ));
+
+        expect_event(bridge::RawEvent::GenericTransfer(
+            dest_chain,
+            1,
+            resource_id,
+            metadata,
+            Some(1),
         ));
     })
 }
@@ -56,7 +101,7 @@ fn transfer_native() {
         let dest_chain = 0;
         let resource_id = NativeTokenId::get();
         let amount: u64 = 100;
-        let recipient = vec![99];
+        let recipient = vec![99; 20];
 
         assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain.clone()));
         assert_ok!(Example::transfer_native(
@@ -65,6 +110,11 @@ fn transfer_native() {
             recipient.clone(),
             dest_chain,
         ));
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE - amount);
+        assert_eq!(
+            Balances::free_balance(Bridge::account_id()),
+            ENDOWED_BALANCE + amount
+        );
 
         expect_event(bridge::RawEvent::FungibleTransfer(
             dest_chain,
@@ -72,7 +122,263 @@ fn transfer_native() {
             resource_id,
             amount.into(),
             recipient,
+            vec![],
+            Some(RELAYER_A),
+        ));
+    })
+}
+
+#[test]
+fn transfer_native_reserve_custody() {
+    new_test_ext().execute_with(|| {
+        Custody::set(CustodyMode::Reserve);
+        let dest_chain = 0;
+        let resource_id = NativeTokenId::get();
+        let amount: u64 = 100;
+        let bridge_id: u64 = Bridge::account_id();
+
+        // Funds stay with the sender, locked
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_A),
+            amount,
+            vec![99; 20],
+            dest_chain,
+        ));
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE - amount);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), amount);
+        assert_eq!(Example::bridged_reserve(RELAYER_A), amount);
+        assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE);
+        assert_eq!(Balances::reserved_balance(bridge_id), 0);
+
+        // Inbound delivery unreserves the recipient's bridged funds, leaving reserves made
+        // for other reasons alone
+        assert_ok!(Balances::reserve(&RELAYER_A, 500));
+        assert_ok!(Example::transfer(
+            Origin::signed(bridge_id),
+            RELAYER_A,
+            60,
+            resource_id,
+        ));
+        assert_eq!(
+            Balances::free_balance(RELAYER_A),
+            ENDOWED_BALANCE - amount - 500 + 60
+        );
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 500 + 40);
+        assert_eq!(Example::bridged_reserve(RELAYER_A), 40);
+        assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE);
+        expect_event(RawEvent::FungibleReleased(RELAYER_A, 60, resource_id));
+
+        assert_noop!(
+            Example::transfer(Origin::signed(bridge_id), RELAYER_A, 41, resource_id),
+            Error::<Test>::InsufficientReserved
+        );
+        // Accounts without bridged funds can't be paid, even from the bridge account
+        assert_noop!(
+            Example::transfer(Origin::signed(bridge_id), RELAYER_B, 10, resource_id),
+            Error::<Test>::InsufficientReserved
+        );
+
+        assert_ok!(Example::transfer(
+            Origin::signed(bridge_id),
+            RELAYER_A,
+            40,
+            resource_id,
+        ));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 500);
+        assert_eq!(Example::bridged_reserve(RELAYER_A), 0);
+    })
+}
+
+#[test]
+fn cancel_native_transfer_reserve_custody() {
+    new_test_ext().execute_with(|| {
+        Custody::set(CustodyMode::Reserve);
+        let dest_chain = 0;
+        let amount: u64 = 100;
+        let bridge_id: u64 = Bridge::account_id();
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_A),
+            amount,
+            vec![99; 20],
+            dest_chain,
+        ));
+        System::set_block_number(1 + CancelDelay::get());
+        assert_ok!(Example::cancel_native_transfer(
+            Origin::signed(RELAYER_A),
+            dest_chain,
+            1
         ));
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 0);
+        assert_eq!(Example::bridged_reserve(RELAYER_A), 0);
+        assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE);
+    })
+}
+
+/// Mints the token used by `assert_transfers_fail` and whitelists `dest_chain`
+fn setup_failing_transfers(dest_chain: bridge::ChainId, token_id: U256) {
+    assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+    assert_ok!(Erc721::mint(Origin::root(), RELAYER_A, token_id, vec![1]));
+}
+
+/// Asserts that native and ERC721 transfers to `dest_chain` are rejected with `error`, without
+/// moving any funds or burning the token
+fn assert_transfers_fail(dest_chain: bridge::ChainId, token_id: U256, error: DispatchError) {
+    let bridge_id: u64 = Bridge::account_id();
+    assert_noop!(
+        Example::transfer_native(Origin::signed(RELAYER_A), 100, vec![99; 20], dest_chain),
+        error
+    );
+    assert_noop!(
+        Example::transfer_erc721(
+            Origin::signed(RELAYER_A),
+            vec![99; 20],
+            token_id,
+            dest_chain
+        ),
+        error
+    );
+    assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+    assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE);
+    assert_eq!(Erc721::owner_of(token_id), Some(RELAYER_A));
+}
+
+#[test]
+fn transfers_revert_when_bridge_paused() {
+    new_test_ext().execute_with(|| {
+        let token_id = U256::from(100);
+        setup_failing_transfers(0, token_id);
+        assert_ok!(Bridge::set_paused(Origin::root(), true));
+
+        assert_transfers_fail(0, token_id, bridge::Error::<Test>::BridgeIsPaused.into());
+    })
+}
+
+#[test]
+fn transfers_revert_when_chain_paused() {
+    new_test_ext().execute_with(|| {
+        let token_id = U256::from(100);
+        setup_failing_transfers(0, token_id);
+        assert_ok!(Bridge::set_chain_paused(Origin::root(), 0, true));
+
+        assert_transfers_fail(0, token_id, bridge::Error::<Test>::ChainPaused.into());
+    })
+}
+
+#[test]
+fn transfers_revert_when_resource_disabled() {
+    new_test_ext().execute_with(|| {
+        let token_id = U256::from(100);
+        setup_failing_transfers(0, token_id);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 1));
+        for (r_id, method) in &[
+            (NativeTokenId::get(), b"Example.transfer".to_vec()),
+            (Erc721Id::get(), b"Example.mint_erc721".to_vec()),
+        ] {
+            assert_ok!(Bridge::set_resource(Origin::root(), *r_id, method.clone()));
+            assert_ok!(Bridge::set_resource_enabled(Origin::root(), *r_id, false));
+        }
+
+        assert_transfers_fail(0, token_id, bridge::Error::<Test>::ResourceDisabled.into());
+    })
+}
+
+#[test]
+fn transfers_revert_when_resource_paused() {
+    new_test_ext().execute_with(|| {
+        let token_id = U256::from(100);
+        setup_failing_transfers(0, token_id);
+        for r_id in &[NativeTokenId::get(), Erc721Id::get()] {
+            assert_ok!(Bridge::set_resource_paused(Origin::root(), *r_id, true));
+        }
+
+        assert_transfers_fail(0, token_id, bridge::Error::<Test>::ResourcePaused.into());
+    })
+}
+
+#[test]
+fn transfer_erc721_reverts_on_bridge_bounds() {
+    new_test_ext().execute_with(|| {
+        let token_id = U256::from(100);
+        setup_failing_transfers(0, token_id);
+        let transfer =
+            || Example::transfer_erc721(Origin::signed(RELAYER_A), vec![99; 20], token_id, 0);
+
+        MaxNftMetadataLen::set(0);
+        assert_noop!(transfer(), bridge::Error::<Test>::MetadataTooLong);
+        assert_eq!(Erc721::owner_of(token_id), Some(RELAYER_A));
+
+        MaxNftMetadataLen::set(64);
+        MaxTokenIdLen::set(16);
+        assert_noop!(transfer(), bridge::Error::<Test>::TokenIdTooLong);
+        assert_eq!(Erc721::owner_of(token_id), Some(RELAYER_A));
+    })
+}
+
+#[test]
+fn transfer_native_disabled() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        EnableNativeTransfer::set(false);
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_noop!(
+            Example::transfer_native(Origin::signed(RELAYER_A), 100, vec![99; 20], dest_chain),
+            Error::<Test>::NativeTransferDisabled
+        );
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+    })
+}
+
+#[test]
+fn proposal_transfer_depth_limit_reverts() {
+    new_test_ext().execute_with(|| {
+        Custody::set(CustodyMode::Reserve);
+        MaxBridgeDepth::set(0);
+        let src_id = 1;
+        let bridge_id: u64 = Bridge::account_id();
+        let token_id = U256::from(100);
+        assert_ok!(Erc721::mint(Origin::root(), bridge_id, token_id, vec![1]));
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 0));
+
+        let proposals = vec![
+            (
+                b"native".to_vec(),
+                b"Example.transfer_native".to_vec(),
+                Call::Example(crate::Call::transfer_native(100, vec![99; 20], 0)),
+            ),
+            (
+                b"nft".to_vec(),
+                b"Example.transfer_erc721".to_vec(),
+                Call::Example(crate::Call::transfer_erc721(vec![99; 20], token_id, 0)),
+            ),
+        ];
+        for (nonce, (name, method, proposal)) in proposals.into_iter().enumerate() {
+            let r_id = bridge::derive_resource_id(src_id, &name);
+            assert_ok!(Bridge::set_resource(Origin::root(), r_id, method));
+            assert_err!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    nonce as u64 + 1,
+                    bridge::SrcChainId(src_id),
+                    r_id,
+                    Box::new(proposal),
+                    vec![]
+                ),
+                bridge::Error::<Test>::MaxDepthExceeded
+            );
+        }
+
+        // Neither the funds nor the token left the bridge account
+        assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE);
+        assert_eq!(Balances::reserved_balance(bridge_id), 0);
+        assert_eq!(Erc721::owner_of(token_id), Some(bridge_id));
     })
 }
 
@@ -85,7 +391,7 @@ fn transfer_erc721() {
         let token_id_slice: &mut [u8] = &mut [0; 32];
         token_id.to_big_endian(token_id_slice);
         let metadata: Vec<u8> = vec![1, 2, 3, 4];
-        let recipient = vec![99];
+        let recipient = vec![99; 20];
 
         // Create a token
         assert_ok!(Erc721::mint(
@@ -118,6 +424,7 @@ fn transfer_erc721() {
             token_id_slice.to_vec(),
             recipient.clone(),
             metadata,
+            Some(RELAYER_A),
         ));
 
         // Ensure token no longer exists
@@ -155,22 +462,76 @@ fn execute_remark() {
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
             prop_id,
-            src_id,
+            bridge::SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_B),
             prop_id,
-            src_id,
+            bridge::SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
 
         event_exists(RawEvent::Remark(hash));
     })
 }
 
+#[test]
+fn proposal_transfer_depth_limit() {
+    new_test_ext().execute_with(|| {
+        let hash: H256 = "ABC".using_encoded(blake2_256).into();
+        let proposal = Call::Example(crate::Call::transfer_hash(hash, 0));
+        let src_id = 1;
+        let r_id = bridge::derive_resource_id(src_id, b"hash");
+        let resource = b"Example.transfer_hash".to_vec();
+        let bridge_id: u64 = Bridge::account_id();
+
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 0));
+        assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
+
+        // Executed proposals may not initiate transfers
+        MaxBridgeDepth::set(0);
+        assert_err!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                bridge::SrcChainId(src_id),
+                r_id,
+                Box::new(proposal.clone()),
+                vec![]
+            ),
+            bridge::Error::<Test>::MaxDepthExceeded
+        );
+        assert_eq!(Bridge::bridge_call_depth(), 0);
+
+        // A transfer from a top-level proposal is allowed
+        MaxBridgeDepth::set(1);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            bridge::SrcChainId(src_id),
+            r_id,
+            Box::new(proposal.clone()),
+            vec![]
+        ));
+        event_exists(bridge::RawEvent::GenericTransfer(
+            0,
+            1,
+            HashId::get(),
+            hash.as_ref().to_vec(),
+            Some(bridge_id),
+        ));
+        assert_eq!(Bridge::bridge_call_depth(), 0);
+    })
+}
+
 #[test]
 fn execute_remark_bad_origin() {
     new_test_ext().execute_with(|| {
@@ -211,11 +572,14 @@ fn transfer() {
         assert_eq!(Balances::free_balance(&bridge_id), ENDOWED_BALANCE - 10);
         assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE + 10);
 
-        assert_events(vec![Event::balances(balances::Event::Transfer(
-            Bridge::account_id(),
-            RELAYER_A,
-            10,
-        ))]);
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(
+                Bridge::account_id(),
+                RELAYER_A,
+                10,
+            )),
+            Event::example(RawEvent::FungibleReleased(RELAYER_A, 10, resource_id)),
+        ]);
     })
 }
 
@@ -279,16 +643,20 @@ fn create_sucessful_transfer_proposal() {
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
             prop_id,
-            src_id,
+            bridge::SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
         let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![].try_into().unwrap(),
             status: bridge::ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
         };
         assert_eq!(prop, expected);
 
@@ -296,16 +664,19 @@ fn create_sucessful_transfer_proposal() {
         assert_ok!(Bridge::reject_proposal(
             Origin::signed(RELAYER_B),
             prop_id,
-            src_id,
+            bridge::SrcChainId(src_id),
             r_id,
             Box::new(proposal.clone())
         ));
         let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B],
+            votes_for: vec![RELAYER_A].try_into().unwrap(),
+            votes_against: vec![RELAYER_B].try_into().unwrap(),
             status: bridge::ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
         };
         assert_eq!(prop, expected);
 
@@ -313,16 +684,20 @@ fn create_sucessful_transfer_proposal() {
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_C),
             prop_id,
-            src_id,
+            bridge::SrcChainId(src_id),
             r_id,
-            Box::new(proposal.clone())
+            Box::new(proposal.clone()),
+            vec![]
         ));
         let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
         let expected = bridge::ProposalVotes {
-            votes_for: vec![RELAYER_A, RELAYER_C],
-            votes_against: vec![RELAYER_B],
+            votes_for: vec![RELAYER_A, RELAYER_C].try_into().unwrap(),
+            votes_against: vec![RELAYER_B].try_into().unwrap(),
             status: bridge::ProposalStatus::Approved,
             expiry: ProposalLifetime::get() + 1,
+            resource_id: r_id,
+            note: vec![],
+            created_at: 1,
         };
         assert_eq!(prop, expected);
 
@@ -333,16 +708,309 @@ fn create_sucessful_transfer_proposal() {
         );
 
         assert_events(vec![
-            Event::bridge(bridge::RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
-            Event::bridge(bridge::RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
-            Event::bridge(bridge::RawEvent::VoteFor(src_id, prop_id, RELAYER_C)),
+            Event::bridge(bridge::RawEvent::VoteFor(src_id, prop_id, RELAYER_A, None)),
+            Event::bridge(bridge::RawEvent::VoteAgainst(
+                src_id, prop_id, RELAYER_B, None,
+            )),
+            Event::bridge(bridge::RawEvent::VoteFor(src_id, prop_id, RELAYER_C, None)),
             Event::bridge(bridge::RawEvent::ProposalApproved(src_id, prop_id)),
             Event::balances(balances::Event::Transfer(
                 Bridge::account_id(),
                 RELAYER_A,
                 10,
             )),
+            Event::example(RawEvent::FungibleReleased(RELAYER_A, 10, HashId::get())),
             Event::bridge(bridge::RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(bridge::RawEvent::ProposalExecuted(
+                src_id,
+                prop_id,
+                proposal.get_dispatch_info().weight,
+            )),
         ]);
     })
 }
+
+#[test]
+fn checked_amount_conversion() {
+    let max = U256::from(u128::MAX);
+    assert_eq!(
+        <CheckedAmount as AmountConverter<u128>>::from_u256(max),
+        Some(u128::MAX)
+    );
+    assert_eq!(
+        <CheckedAmount as AmountConverter<u128>>::from_u256(max + 1),
+        None
+    );
+    assert_eq!(
+        <CheckedAmount as AmountConverter<u64>>::from_u256(max),
+        None
+    );
+    assert_eq!(
+        <CheckedAmount as AmountConverter<u128>>::to_u256(u128::MAX),
+        Some(max)
+    );
+}
+
+#[test]
+fn route_calls() {
+    new_test_ext().execute_with(|| {
+        let hash: H256 = "ABC".using_encoded(blake2_256).into();
+        let token_id = U256::from(99);
+        let metadata = vec![1, 2, 3];
+
+        assert_eq!(
+            Example::route_call(HashId::get(), &hash.encode()),
+            Some(make_remark_proposal(hash))
+        );
+        assert_eq!(
+            Example::route_call(NativeTokenId::get(), &(RELAYER_A, U256::from(10)).encode()),
+            Some(Call::Example(crate::Call::transfer(
+                RELAYER_A,
+                10,
+                NativeTokenId::get()
+            )))
+        );
+        assert_eq!(
+            Example::route_call(
+                Erc721Id::get(),
+                &(RELAYER_A, token_id, metadata.clone()).encode()
+            ),
+            Some(Call::Example(crate::Call::mint_erc721(
+                RELAYER_A,
+                token_id,
+                metadata,
+                Erc721Id::get()
+            )))
+        );
+
+        // Unknown resources and malformed payloads aren't routed
+        assert_eq!(Example::route_call([0; 32], &hash.encode()), None);
+        assert_eq!(Example::route_call(NativeTokenId::get(), &[1, 2]), None);
+        // Amounts that don't fit in the balance type aren't truncated
+        for amount in &[U256::from(u64::MAX) + 1, U256::from(u128::MAX), U256::MAX] {
+            assert_eq!(
+                Example::route_call(NativeTokenId::get(), &(RELAYER_A, *amount).encode()),
+                None
+            );
+        }
+        assert_eq!(
+            Example::route_call(
+                NativeTokenId::get(),
+                &(RELAYER_A, U256::from(u64::MAX)).encode()
+            ),
+            Some(Call::Example(crate::Call::transfer(
+                RELAYER_A,
+                u64::MAX,
+                NativeTokenId::get()
+            )))
+        );
+        assert_eq!(
+            Example::route_call(HashId::get(), &(hash, 1u8).encode()),
+            None
+        );
+    })
+}
+
+#[test]
+fn cancel_native_transfer() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let amount: u64 = 100;
+        let bridge_id: u64 = Bridge::account_id();
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_A),
+            amount,
+            vec![99; 20],
+            dest_chain,
+        ));
+        assert_eq!(
+            Example::pending_native_transfers(dest_chain, 1),
+            Some((RELAYER_A, amount, 1))
+        );
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE - amount);
+
+        assert_noop!(
+            Example::cancel_native_transfer(Origin::signed(RELAYER_A), dest_chain, 1),
+            Error::<Test>::CancelTooEarly
+        );
+        System::set_block_number(1 + CancelDelay::get());
+        assert_noop!(
+            Example::cancel_native_transfer(Origin::signed(RELAYER_B), dest_chain, 1),
+            Error::<Test>::NotTransferSender
+        );
+        assert_noop!(
+            Example::cancel_native_transfer(Origin::signed(RELAYER_A), dest_chain, 2),
+            Error::<Test>::TransferNotPending
+        );
+
+        assert_ok!(Example::cancel_native_transfer(
+            Origin::signed(RELAYER_A),
+            dest_chain,
+            1
+        ));
+        assert_eq!(Example::pending_native_transfers(dest_chain, 1), None);
+        assert_eq!(Example::pending_native_transfer_count(RELAYER_A), 0);
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+        assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE);
+        expect_event(RawEvent::NativeTransferCancelled(dest_chain, 1, RELAYER_A));
+    })
+}
+
+#[test]
+fn confirmed_native_transfer_cannot_be_cancelled() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let resource_id = NativeTokenId::get();
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_A),
+            100,
+            vec![99; 20],
+            dest_chain,
+        ));
+
+        assert_noop!(
+            Example::confirm_native_transfer(Origin::signed(RELAYER_A), dest_chain, 1, resource_id),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Example::confirm_native_transfer(
+            Origin::signed(Bridge::account_id()),
+            dest_chain,
+            1,
+            resource_id
+        ));
+        expect_event(RawEvent::NativeTransferConfirmed(dest_chain, 1));
+        assert_eq!(Example::pending_native_transfer_count(RELAYER_A), 0);
+
+        System::set_block_number(1 + CancelDelay::get());
+        assert_noop!(
+            Example::cancel_native_transfer(Origin::signed(RELAYER_A), dest_chain, 1),
+            Error::<Test>::TransferNotPending
+        );
+    })
+}
+
+#[test]
+fn pending_native_transfers_per_sender_limit() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let resource_id = NativeTokenId::get();
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+
+        for _ in 0..MaxPendingNativeTransfers::get() {
+            assert_ok!(Example::transfer_native(
+                Origin::signed(RELAYER_A),
+                100,
+                vec![99; 20],
+                dest_chain,
+            ));
+        }
+        assert_eq!(
+            Example::pending_native_transfer_count(RELAYER_A),
+            MaxPendingNativeTransfers::get()
+        );
+        assert_noop!(
+            Example::transfer_native(Origin::signed(RELAYER_A), 100, vec![99; 20], dest_chain),
+            Error::<Test>::TooManyPendingTransfers
+        );
+        // Other senders aren't affected
+        Balances::make_free_balance_be(&RELAYER_B, ENDOWED_BALANCE);
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_B),
+            100,
+            vec![99; 20],
+            dest_chain,
+        ));
+
+        // Resolving a transfer frees a slot
+        assert_ok!(Example::confirm_native_transfer(
+            Origin::signed(Bridge::account_id()),
+            dest_chain,
+            1,
+            resource_id
+        ));
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_A),
+            100,
+            vec![99; 20],
+            dest_chain,
+        ));
+    })
+}
+
+#[test]
+fn transfer_invalid_recipient() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let token_id = U256::from(100);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Erc721::mint(Origin::root(), RELAYER_A, token_id, vec![]));
+
+        for recipient in &[vec![], vec![99; 19], vec![99; 32]] {
+            assert_noop!(
+                Example::transfer_native(
+                    Origin::signed(RELAYER_A),
+                    100,
+                    recipient.clone(),
+                    dest_chain
+                ),
+                Error::<Test>::InvalidRecipient
+            );
+            assert_noop!(
+                Example::transfer_erc721(
+                    Origin::signed(RELAYER_A),
+                    recipient.clone(),
+                    token_id,
+                    dest_chain
+                ),
+                Error::<Test>::InvalidRecipient
+            );
+        }
+
+        assert_ok!(Example::transfer_erc721(
+            Origin::signed(RELAYER_A),
+            vec![99; 20],
+            token_id,
+            dest_chain
+        ));
+    })
+}
+
+#[test]
+fn withdraw_fees() {
+    new_test_ext().execute_with(|| {
+        let dest_chain = 0;
+        let fee_account = Bridge::fee_account_id();
+        NativeTransferFee::set(50);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+
+        // Each native transfer pays the fee on top of the bridged amount
+        for _ in 0..2 {
+            assert_ok!(Example::transfer_native(
+                Origin::signed(RELAYER_A),
+                100,
+                vec![99; 20],
+                dest_chain,
+            ));
+        }
+        assert_eq!(Balances::free_balance(fee_account), 100);
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE - 300);
+
+        assert_noop!(
+            Example::withdraw_fees(Origin::signed(RELAYER_A), RELAYER_B, 40),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Example::withdraw_fees(Origin::root(), RELAYER_B, 101),
+            Error::<Test>::InsufficientFees
+        );
+
+        assert_ok!(Example::withdraw_fees(Origin::root(), RELAYER_B, 40));
+        assert_eq!(Balances::free_balance(fee_account), 60);
+        assert_eq!(Balances::free_balance(RELAYER_B), 40);
+        expect_event(RawEvent::FeesWithdrawn(RELAYER_B, 40));
+    })
+}